@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Who performed an audited action.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditActor {
+    /// The id of the user, if the actor is a user.
+    pub id: Option<String>,
+    /// The email or phone number of the actor.
+    pub username: Option<String>,
+    /// The display name of the actor, if any.
+    pub name: Option<String>,
+}
+
+/// Represents an entry of the GoTrue audit log.
+///
+/// Entries serialize to the same shape GoTrue returns, with the actor and
+/// action nested in a `payload`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawAuditLogEntry", into = "RawAuditLogEntry")]
+pub struct AuditLogEntry {
+    /// The id of the entry.
+    pub id: String,
+    /// Who performed the action.
+    pub actor: AuditActor,
+    /// What was done, e.g. `login`, `logout` or `user_signedup`.
+    pub action: String,
+    /// The category of the action, e.g. `account`, `token` or `team`.
+    pub log_type: String,
+    /// Details specific to the action, such as the provider signed in with.
+    pub traits: Option<Value>,
+    /// The IP address the action was performed from.
+    pub ip_address: String,
+    /// The date of the action.
+    pub created_at: String,
+}
+
+/// An audit log entry as returned by GoTrue, with its details in a payload.
+#[derive(Serialize, Deserialize)]
+struct RawAuditLogEntry {
+    id: String,
+    #[serde(default)]
+    payload: RawPayload,
+    #[serde(default)]
+    ip_address: String,
+    created_at: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawPayload {
+    actor_id: Option<String>,
+    actor_username: Option<String>,
+    actor_name: Option<String>,
+    #[serde(default)]
+    action: String,
+    #[serde(default)]
+    log_type: String,
+    traits: Option<Value>,
+}
+
+impl From<RawAuditLogEntry> for AuditLogEntry {
+    fn from(raw: RawAuditLogEntry) -> Self {
+        AuditLogEntry {
+            id: raw.id,
+            actor: AuditActor {
+                id: raw.payload.actor_id,
+                username: raw.payload.actor_username,
+                name: raw.payload.actor_name,
+            },
+            action: raw.payload.action,
+            log_type: raw.payload.log_type,
+            traits: raw.payload.traits,
+            ip_address: raw.ip_address,
+            created_at: raw.created_at,
+        }
+    }
+}
+
+impl From<AuditLogEntry> for RawAuditLogEntry {
+    fn from(entry: AuditLogEntry) -> Self {
+        RawAuditLogEntry {
+            id: entry.id,
+            payload: RawPayload {
+                actor_id: entry.actor.id,
+                actor_username: entry.actor.username,
+                actor_name: entry.actor.name,
+                action: entry.action,
+                log_type: entry.log_type,
+                traits: entry.traits,
+            },
+            ip_address: entry.ip_address,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// Represents a page of entries returned by [`crate::Client::list_audit_log`].
+#[derive(Debug, Clone)]
+pub struct AuditLogPage {
+    /// The entries on this page, newest first.
+    pub entries: Vec<AuditLogEntry>,
+    /// The total number of entries matching the query, if GoTrue reported it.
+    pub total: Option<u64>,
+    /// The next page, if there is one.
+    pub next_page: Option<u32>,
+}
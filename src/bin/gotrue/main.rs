@@ -0,0 +1,426 @@
+//! `gotrue`, a command-line admin tool for GoTrue built on [`go_true_redux::Client`].
+//!
+//! The URL and service role key are read from `--url` and `--service-key`, or
+//! from the `GOTRUE_URL` and `GOTRUE_SERVICE_KEY` environment variables.
+
+mod output;
+
+use std::{process::ExitCode, time::Duration};
+
+use argh::FromArgs;
+use futures_util::TryStreamExt;
+use go_true_redux::{
+    AdminUserAttributes, Client, ExportFormat, GenerateLinkOptions, GenerateLinkParams,
+    ListUsersQuery, User,
+};
+use tokio::{
+    fs::File,
+    io::{self, AsyncWrite, AsyncWriteExt, BufWriter},
+};
+
+use output::{Format, Table};
+
+/// The ban duration of `ban-user`, about a hundred years.
+const FOREVER: &str = "876000h";
+
+#[derive(FromArgs)]
+/// Administers the users of a GoTrue instance.
+struct Args {
+    /// the URL of GoTrue, defaults to $GOTRUE_URL
+    #[argh(option)]
+    url: Option<String>,
+
+    /// the service role key, defaults to $GOTRUE_SERVICE_KEY
+    #[argh(option)]
+    service_key: Option<String>,
+
+    /// the output format, `table` (default) or `json`
+    #[argh(option, default = "Format::Table")]
+    output: Format,
+
+    #[argh(subcommand)]
+    command: Command,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    ListUsers(ListUsers),
+    SearchUsers(SearchUsers),
+    ExportUsers(ExportUsers),
+    GetUser(GetUser),
+    CreateUser(CreateUser),
+    InviteUser(InviteUser),
+    DeleteUser(DeleteUser),
+    BanUser(BanUser),
+    UnbanUser(UnbanUser),
+    ConfirmEmail(ConfirmEmail),
+    GenerateLink(GenerateLink),
+    ShowSettings(ShowSettings),
+    ShowHealth(ShowHealth),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list-users")]
+/// Lists the users, one page at a time.
+struct ListUsers {
+    /// the page to show, starting at 1
+    #[argh(option)]
+    page: Option<u32>,
+
+    /// the number of users per page
+    #[argh(option)]
+    per_page: Option<u32>,
+
+    /// list every user instead of one page
+    #[argh(switch)]
+    all: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "search-users")]
+/// Lists every user whose email or name contains the text.
+struct SearchUsers {
+    /// the text to search for
+    #[argh(positional)]
+    text: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "export-users")]
+/// Writes every user as JSON lines or CSV.
+struct ExportUsers {
+    /// the export format, `jsonl` (default) or `csv`
+    #[argh(option, default = "String::from(\"jsonl\")")]
+    format: String,
+
+    /// the file to write to, defaults to stdout
+    #[argh(option)]
+    file: Option<String>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "get-user")]
+/// Shows a user.
+struct GetUser {
+    /// the id of the user
+    #[argh(positional)]
+    id: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "create-user")]
+/// Creates a user.
+struct CreateUser {
+    /// the email of the user
+    #[argh(option)]
+    email: Option<String>,
+
+    /// the phone number of the user
+    #[argh(option)]
+    phone: Option<String>,
+
+    /// the password of the user
+    #[argh(option)]
+    password: Option<String>,
+
+    /// mark the email or phone number as confirmed
+    #[argh(switch)]
+    confirm: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "invite-user")]
+/// Invites a user by email.
+struct InviteUser {
+    /// the email of the user
+    #[argh(positional)]
+    email: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete-user")]
+/// Deletes a user.
+struct DeleteUser {
+    /// the id of the user
+    #[argh(positional)]
+    id: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "ban-user")]
+/// Bans a user from signing in.
+struct BanUser {
+    /// the id of the user
+    #[argh(positional)]
+    id: String,
+
+    /// how long to ban the user for, e.g. `24h`, defaults to forever
+    #[argh(option)]
+    duration: Option<String>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "unban-user")]
+/// Lifts the ban of a user.
+struct UnbanUser {
+    /// the id of the user
+    #[argh(positional)]
+    id: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "confirm-email")]
+/// Confirms the email of a user without sending a confirmation.
+struct ConfirmEmail {
+    /// the id of the user
+    #[argh(positional)]
+    id: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "generate-link")]
+/// Generates an action link without emailing it.
+struct GenerateLink {
+    /// the link type: signup, invite, magiclink, recovery,
+    /// email_change_current or email_change_new
+    #[argh(positional)]
+    link_type: String,
+
+    /// the email of the user
+    #[argh(positional)]
+    email: String,
+
+    /// the password of a signup
+    #[argh(option)]
+    password: Option<String>,
+
+    /// the email to change to
+    #[argh(option)]
+    new_email: Option<String>,
+
+    /// where to send the user after they follow the link
+    #[argh(option)]
+    redirect_to: Option<String>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "settings")]
+/// Shows the public settings of the instance.
+struct ShowSettings {}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "health")]
+/// Shows the version of the instance, failing if it is down.
+struct ShowHealth {
+    /// how many seconds to wait for the instance to come up
+    #[argh(option)]
+    wait: Option<u64>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Args = argh::from_env();
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn client(args: &Args) -> Result<Client, String> {
+    let url = args
+        .url
+        .clone()
+        .or_else(|| std::env::var("GOTRUE_URL").ok())
+        .ok_or("no GoTrue URL, set --url or GOTRUE_URL")?;
+
+    let client = Client::new(&url);
+    match args
+        .service_key
+        .clone()
+        .or_else(|| std::env::var("GOTRUE_SERVICE_KEY").ok())
+    {
+        Some(key) => Ok(client
+            .with_header("Authorization", format!("Bearer {key}"))
+            .with_header("apikey", key)),
+        None => Ok(client),
+    }
+}
+
+async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let client = client(&args)?;
+    let format = args.output;
+
+    match args.command {
+        Command::ListUsers(list) => {
+            let mut query = ListUsersQuery::default();
+            if let Some(per_page) = list.per_page {
+                query = query.with_per_page(per_page);
+            }
+
+            if list.all {
+                if list.page.is_some() {
+                    return Err("--page cannot be combined with --all".into());
+                }
+                let users: Vec<User> = client.list_all_users(query).try_collect().await?;
+                format.print_users(&users)?;
+            } else {
+                if let Some(page) = list.page {
+                    query = query.with_page(page);
+                }
+                let page = client.list_users(Some(query)).await?;
+                format.print_users(&page.users)?;
+                if format == Format::Table {
+                    if let Some(next_page) = page.next_page {
+                        eprintln!("more users on page {next_page}");
+                    }
+                }
+            }
+        }
+        Command::SearchUsers(search) => {
+            let query = ListUsersQuery::default().with_filter(search.text);
+            let users: Vec<User> = client.list_all_users(query).try_collect().await?;
+            format.print_users(&users)?;
+        }
+        Command::ExportUsers(export) => {
+            let export_format = match export.format.as_str() {
+                "jsonl" => ExportFormat::JsonLines,
+                "csv" => ExportFormat::csv(),
+                other => return Err(format!("unknown export format `{other}`").into()),
+            };
+            let mut writer: Box<dyn AsyncWrite + Unpin> = match &export.file {
+                Some(path) => Box::new(BufWriter::new(File::create(path).await?)),
+                None => Box::new(io::stdout()),
+            };
+
+            let count = client
+                .export_users(ListUsersQuery::default(), &export_format, &mut writer)
+                .await?;
+            writer.flush().await?;
+            eprintln!("exported {count} users");
+        }
+        Command::GetUser(get) => {
+            let user = client.get_user_by_id(&get.id).await?;
+            format.print_user(&user)?;
+        }
+        Command::CreateUser(create) => {
+            let confirm = create.confirm.then_some(true);
+            let attributes = AdminUserAttributes {
+                email_confirm: create.email.as_ref().and(confirm),
+                phone_confirm: create.phone.as_ref().and(confirm),
+                email: create.email,
+                phone: create.phone,
+                password: create.password,
+                ..Default::default()
+            };
+            let user = client.admin_create_user(&attributes).await?;
+            format.print_user(&user)?;
+        }
+        Command::InviteUser(invite) => {
+            let user = client.invite_user_by_email(&invite.email).await?;
+            format.print_user(&user)?;
+        }
+        Command::DeleteUser(delete) => {
+            client.delete_user(&delete.id).await?;
+            eprintln!("deleted user {}", delete.id);
+        }
+        Command::BanUser(ban) => {
+            let attributes = AdminUserAttributes {
+                ban_duration: Some(ban.duration.unwrap_or_else(|| FOREVER.to_owned())),
+                ..Default::default()
+            };
+            let user = client.update_user_by_id(&ban.id, attributes).await?;
+            format.print_user(&user)?;
+        }
+        Command::UnbanUser(unban) => {
+            let attributes = AdminUserAttributes {
+                ban_duration: Some("none".to_owned()),
+                ..Default::default()
+            };
+            let user = client.update_user_by_id(&unban.id, attributes).await?;
+            format.print_user(&user)?;
+        }
+        Command::ConfirmEmail(confirm) => {
+            let attributes = AdminUserAttributes {
+                email_confirm: Some(true),
+                ..Default::default()
+            };
+            let user = client.update_user_by_id(&confirm.id, attributes).await?;
+            format.print_user(&user)?;
+        }
+        Command::GenerateLink(generate) => {
+            let params = link_params(&generate)?;
+            let options = GenerateLinkOptions {
+                redirect_to: generate.redirect_to,
+                ..Default::default()
+            };
+            let link = client
+                .generate_email_action_link(&params, Some(options))
+                .await?;
+
+            match format {
+                Format::Json => output::print_json(&link)?,
+                Format::Table => Table::new(["field", "value"])
+                    .row(["action_link", &link.action_link])
+                    .row(["email_otp", link.email_otp.as_deref().unwrap_or("")])
+                    .row(["user_id", &link.user.id])
+                    .print()?,
+            }
+        }
+        Command::ShowSettings(_) => {
+            let settings = client.get_settings().await?;
+            format.print_settings(&settings)?;
+        }
+        Command::ShowHealth(health) => {
+            let health = match health.wait {
+                Some(wait) => client.wait_until_ready(Duration::from_secs(wait)).await?,
+                None => client.health().await?,
+            };
+
+            match format {
+                Format::Json => output::print_json(&health)?,
+                Format::Table => Table::new(["name", "version", "description"])
+                    .row([&health.name, &health.version, &health.description])
+                    .print()?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn link_params(generate: &GenerateLink) -> Result<GenerateLinkParams, String> {
+    let email = generate.email.clone();
+    let new_email = || {
+        generate
+            .new_email
+            .clone()
+            .ok_or_else(|| format!("`{}` links need --new-email", generate.link_type))
+    };
+
+    Ok(match generate.link_type.as_str() {
+        "signup" => GenerateLinkParams::Signup {
+            email,
+            password: generate
+                .password
+                .clone()
+                .ok_or("`signup` links need --password")?,
+        },
+        "invite" => GenerateLinkParams::Invite { email },
+        "magiclink" => GenerateLinkParams::MagicLink { email },
+        "recovery" => GenerateLinkParams::Recovery { email },
+        "email_change_current" => GenerateLinkParams::EmailChangeCurrent {
+            email,
+            new_email: new_email()?,
+        },
+        "email_change_new" => GenerateLinkParams::EmailChangeNew {
+            email,
+            new_email: new_email()?,
+        },
+        other => return Err(format!("unknown link type `{other}`")),
+    })
+}
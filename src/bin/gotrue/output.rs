@@ -0,0 +1,163 @@
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+use go_true_redux::{Settings, User};
+use serde::Serialize;
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Aligned columns, for people.
+    Table,
+    /// Pretty printed JSON, for scripts.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            other => Err(format!("unknown output format `{other}`")),
+        }
+    }
+}
+
+impl Format {
+    pub fn print_users(self, users: &[User]) -> io::Result<()> {
+        match self {
+            Format::Json => print_json(users),
+            Format::Table => users
+                .iter()
+                .fold(
+                    Table::new(["id", "email", "phone", "confirmed", "last sign in"]),
+                    |table, user| {
+                        table.row([
+                            user.id.as_str(),
+                            &user.email,
+                            &user.phone,
+                            if user.email_confirmed_at.is_some() {
+                                "yes"
+                            } else {
+                                "no"
+                            },
+                            user.last_sign_in_at.as_deref().unwrap_or(""),
+                        ])
+                    },
+                )
+                .print(),
+        }
+    }
+
+    pub fn print_user(self, user: &User) -> io::Result<()> {
+        match self {
+            Format::Json => print_json(user),
+            Format::Table => Table::new(["field", "value"])
+                .row(["id", &user.id])
+                .row(["email", &user.email])
+                .row(["phone", &user.phone])
+                .row(["role", &user.role])
+                .row([
+                    "email_confirmed_at",
+                    user.email_confirmed_at.as_deref().unwrap_or(""),
+                ])
+                .row([
+                    "last_sign_in_at",
+                    user.last_sign_in_at.as_deref().unwrap_or(""),
+                ])
+                .row(["created_at", &user.created_at])
+                .row(["updated_at", &user.updated_at])
+                .print(),
+        }
+    }
+
+    pub fn print_settings(self, settings: &Settings) -> io::Result<()> {
+        match self {
+            Format::Json => print_json(settings),
+            Format::Table => {
+                let external = &settings.external;
+                let mut table = Table::new(["setting", "value"])
+                    .row(["disable_signup", &settings.disable_signup.to_string()])
+                    .row([
+                        "mailer_autoconfirm",
+                        &settings.autoconfirms_email().to_string(),
+                    ])
+                    .row(["phone_autoconfirm", &settings.phone_autoconfirm.to_string()])
+                    .row([
+                        "sms_provider",
+                        settings.sms_provider.as_deref().unwrap_or(""),
+                    ])
+                    .row(["mfa_enabled", &settings.mfa_enabled.to_string()])
+                    .row(["saml_enabled", &settings.saml_enabled.to_string()])
+                    .row(["external.email", &optional(external.email)])
+                    .row(["external.phone", &optional(external.phone)])
+                    .row([
+                        "external.anonymous_users",
+                        &optional(external.anonymous_users),
+                    ]);
+
+                let mut providers: Vec<_> = external.oauth.iter().collect();
+                providers.sort();
+                for (provider, enabled) in providers {
+                    table = table.row([&format!("external.{provider}"), &enabled.to_string()]);
+                }
+
+                table.print()
+            }
+        }
+    }
+}
+
+/// A setting the instance may not report, left blank when it does not.
+fn optional(value: Option<bool>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+pub fn print_json(value: &(impl Serialize + ?Sized)) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, value)?;
+    writeln!(stdout)
+}
+
+/// A table printed with its columns aligned.
+pub struct Table<const N: usize> {
+    rows: Vec<[String; N]>,
+}
+
+impl<const N: usize> Table<N> {
+    pub fn new(header: [&str; N]) -> Self {
+        Table {
+            rows: vec![header.map(str::to_uppercase)],
+        }
+    }
+
+    pub fn row(mut self, row: [&str; N]) -> Self {
+        self.rows.push(row.map(str::to_owned));
+        self
+    }
+
+    pub fn print(&self) -> io::Result<()> {
+        let mut widths = [0; N];
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut stdout = io::stdout().lock();
+        for row in &self.rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            writeln!(stdout, "{}", cells.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+}
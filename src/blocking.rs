@@ -0,0 +1,444 @@
+//! A synchronous client for code that does not run on an async runtime.
+//!
+//! Enabled by the `blocking` feature. [`Client`] wraps the async
+//! [`crate::Client`] and its own single threaded tokio runtime, and takes and
+//! returns the same types. Like `reqwest::blocking`, it must not be used from
+//! within an async runtime, where blocking on it panics.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use go_true_redux::{blocking::Client, EmailOrPhone};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("http://localhost:9998");
+//!
+//!     let email = EmailOrPhone::Email("email@example.com".to_string());
+//!     let session = client.sign_in(email, "Abcd1234!")?;
+//!
+//!     println!("{:?}", session.user);
+//!     Ok(())
+//! }
+//! ```
+
+use std::{future::Future, io::Write, pin::Pin, sync::Arc, time::Duration};
+
+use futures_util::{stream, Stream, StreamExt};
+use http::header::IntoHeaderName;
+use tokio::runtime::Runtime;
+
+use crate::{
+    client::export_users, AdminUserAttributes, AuditLogEntry, AuditLogPage, AuditLogQuery,
+    Cassette, EmailChangeConfirmation, EmailOrPhone, Error, ExportFormat, GenerateLinkOptions,
+    GenerateLinkParams, GeneratedLink, Health, HttpTransport, IdTokenCredentials, ImportReport,
+    ListUsersQuery, MagicLinkOptions, OtpOptions, RateLimitTracker, ResendOptions, ResendParams,
+    RetryPolicy, Session, Settings, SettingsCache, SignOutScope, SsoOptions, SsoProvider,
+    SsoProviderAttributes, SsoTarget, User, UserAttributes, UserList, Verification, VerifyParams,
+};
+
+/// A blocking GoTrue API client.
+///
+/// Every method blocks on the method of the same name of [`crate::Client`].
+/// Clones share the runtime and the client state, such as a
+/// [`RateLimitTracker`].
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+/// The items of a stream of [`crate::Client`], waited for one at a time.
+struct BlockingIter<'a, T> {
+    runtime: &'a Runtime,
+    stream: Pin<Box<dyn Stream<Item = T> + 'a>>,
+}
+
+impl<T> Iterator for BlockingIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl From<crate::Client> for Client {
+    /// Wraps an async client, starting a runtime for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be started.
+    fn from(inner: crate::Client) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("could not start the tokio runtime");
+
+        Client {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+impl Client {
+    /// Creates a blocking GoTrue API client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be started.
+    #[cfg(feature = "reqwest-transport")]
+    pub fn new(url: &str) -> Client {
+        Client::from(crate::Client::new(url))
+    }
+
+    /// Creates a blocking GoTrue API client sending its requests with the given transport.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be started.
+    pub fn with_transport(url: &str, transport: impl HttpTransport + 'static) -> Client {
+        Client::from(crate::Client::with_transport(url, transport))
+    }
+
+    /// The async client this client blocks on.
+    pub fn inner(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// See [`crate::Client::with_header`].
+    pub fn with_header(
+        mut self,
+        header_name: impl IntoHeaderName,
+        header_value: impl AsRef<str>,
+    ) -> Self {
+        self.inner = self.inner.with_header(header_name, header_value);
+        self
+    }
+
+    /// See [`crate::Client::with_retry_policy`].
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry_policy(policy);
+        self
+    }
+
+    /// See [`crate::Client::with_rate_limit_tracker`].
+    pub fn with_rate_limit_tracker(mut self, tracker: RateLimitTracker) -> Self {
+        self.inner = self.inner.with_rate_limit_tracker(tracker);
+        self
+    }
+
+    /// See [`crate::Client::with_settings_cache`].
+    pub fn with_settings_cache(mut self, cache: SettingsCache) -> Self {
+        self.inner = self.inner.with_settings_cache(cache);
+        self
+    }
+
+    /// See [`crate::Client::with_cassette`].
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.inner = self.inner.with_cassette(cassette);
+        self
+    }
+
+    /// See [`crate::Client::get_url_for_provider`].
+    pub fn get_url_for_provider(&self, provider: &str) -> String {
+        self.inner.get_url_for_provider(provider)
+    }
+
+    /// See [`crate::Client::get_checked_url_for_provider`].
+    pub fn get_checked_url_for_provider(&self, provider: &str) -> Result<String, Error> {
+        self.block_on(self.inner.get_checked_url_for_provider(provider))
+    }
+
+    /// See [`crate::Client::get_settings`].
+    pub fn get_settings(&self) -> Result<Settings, Error> {
+        self.block_on(self.inner.get_settings())
+    }
+
+    /// See [`crate::Client::health`].
+    pub fn health(&self) -> Result<Health, Error> {
+        self.block_on(self.inner.health())
+    }
+
+    /// See [`crate::Client::wait_until_ready`].
+    pub fn wait_until_ready(&self, wait: Duration) -> Result<Health, Error> {
+        self.block_on(self.inner.wait_until_ready(wait))
+    }
+
+    /// See [`crate::Client::sign_up`].
+    pub fn sign_up(&self, email_or_phone: EmailOrPhone, password: &str) -> Result<Session, Error> {
+        self.block_on(self.inner.sign_up(email_or_phone, password))
+    }
+
+    /// See [`crate::Client::sign_in`].
+    pub fn sign_in(&self, email_or_phone: EmailOrPhone, password: &str) -> Result<Session, Error> {
+        self.block_on(self.inner.sign_in(email_or_phone, password))
+    }
+
+    /// See [`crate::Client::sign_in_with_id_token`].
+    pub fn sign_in_with_id_token(
+        &self,
+        credentials: &IdTokenCredentials,
+    ) -> Result<Session, Error> {
+        self.block_on(self.inner.sign_in_with_id_token(credentials))
+    }
+
+    /// See [`crate::Client::sign_in_with_magic_link`].
+    pub fn sign_in_with_magic_link(
+        &self,
+        email: &str,
+        options: &MagicLinkOptions,
+    ) -> Result<bool, Error> {
+        self.block_on(self.inner.sign_in_with_magic_link(email, options))
+    }
+
+    /// See [`crate::Client::sign_in_with_sso`].
+    pub fn sign_in_with_sso(
+        &self,
+        target: SsoTarget,
+        options: &SsoOptions,
+    ) -> Result<String, Error> {
+        self.block_on(self.inner.sign_in_with_sso(target, options))
+    }
+
+    /// See [`crate::Client::exchange_code_for_session`].
+    pub fn exchange_code_for_session(
+        &self,
+        auth_code: &str,
+        code_verifier: &str,
+    ) -> Result<Session, Error> {
+        self.block_on(
+            self.inner
+                .exchange_code_for_session(auth_code, code_verifier),
+        )
+    }
+
+    /// See [`crate::Client::send_otp`].
+    pub fn send_otp(
+        &self,
+        email_or_phone: EmailOrPhone,
+        options: Option<OtpOptions>,
+    ) -> Result<bool, Error> {
+        self.block_on(self.inner.send_otp(email_or_phone, options))
+    }
+
+    /// See [`crate::Client::verify_otp`].
+    pub fn verify_otp(&self, params: VerifyParams) -> Result<Verification, Error> {
+        self.block_on(self.inner.verify_otp(params))
+    }
+
+    /// See [`crate::Client::verify_link`].
+    pub fn verify_link(&self, link: &str) -> Result<Verification, Error> {
+        self.block_on(self.inner.verify_link(link))
+    }
+
+    /// See [`crate::Client::resend`].
+    pub fn resend(
+        &self,
+        params: ResendParams,
+        options: Option<ResendOptions>,
+    ) -> Result<bool, Error> {
+        self.block_on(self.inner.resend(params, options))
+    }
+
+    /// See [`crate::Client::reset_password_for_email`].
+    pub fn reset_password_for_email(&self, email: &str) -> Result<bool, Error> {
+        self.block_on(self.inner.reset_password_for_email(email))
+    }
+
+    /// See [`crate::Client::refresh_access_token`].
+    pub fn refresh_access_token(&self, refresh_token: &str) -> Result<Session, Error> {
+        self.block_on(self.inner.refresh_access_token(refresh_token))
+    }
+
+    /// See [`crate::Client::sign_out`].
+    pub fn sign_out(&self, access_token: &str) -> Result<(), Error> {
+        self.block_on(self.inner.sign_out(access_token))
+    }
+
+    /// See [`crate::Client::sign_out_with_scope`].
+    pub fn sign_out_with_scope(
+        &self,
+        access_token: &str,
+        scope: SignOutScope,
+    ) -> Result<(), Error> {
+        self.block_on(self.inner.sign_out_with_scope(access_token, scope))
+    }
+
+    /// See [`crate::Client::get_user`].
+    pub fn get_user(&self, jwt: &str) -> Result<User, Error> {
+        self.block_on(self.inner.get_user(jwt))
+    }
+
+    /// See [`crate::Client::update_user`].
+    pub fn update_user(&self, user: UserAttributes, jwt: &str) -> Result<User, Error> {
+        self.block_on(self.inner.update_user(user, jwt))
+    }
+
+    /// See [`crate::Client::reauthenticate`].
+    pub fn reauthenticate(&self, jwt: &str) -> Result<bool, Error> {
+        self.block_on(self.inner.reauthenticate(jwt))
+    }
+
+    /// See [`crate::Client::resend_email_change`].
+    pub fn resend_email_change(&self, jwt: &str) -> Result<User, Error> {
+        self.block_on(self.inner.resend_email_change(jwt))
+    }
+
+    /// See [`crate::Client::resend_phone_change`].
+    pub fn resend_phone_change(&self, jwt: &str) -> Result<User, Error> {
+        self.block_on(self.inner.resend_phone_change(jwt))
+    }
+
+    /// See [`crate::Client::verify_email_change`].
+    pub fn verify_email_change(
+        &self,
+        email: &str,
+        token: &str,
+    ) -> Result<EmailChangeConfirmation, Error> {
+        self.block_on(self.inner.verify_email_change(email, token))
+    }
+
+    /// See [`crate::Client::verify_phone_change`].
+    pub fn verify_phone_change(&self, phone: &str, token: &str) -> Result<Session, Error> {
+        self.block_on(self.inner.verify_phone_change(phone, token))
+    }
+
+    /// See [`crate::Client::invite_user_by_email`].
+    pub fn invite_user_by_email(&self, email: &str) -> Result<User, Error> {
+        self.block_on(self.inner.invite_user_by_email(email))
+    }
+
+    /// See [`crate::Client::create_user`].
+    pub fn create_user(&self, user: User) -> Result<User, Error> {
+        self.block_on(self.inner.create_user(user))
+    }
+
+    /// See [`crate::Client::admin_create_user`].
+    pub fn admin_create_user(&self, attributes: &AdminUserAttributes) -> Result<User, Error> {
+        self.block_on(self.inner.admin_create_user(attributes))
+    }
+
+    /// See [`crate::Client::generate_email_action_link`].
+    pub fn generate_email_action_link(
+        &self,
+        params: &GenerateLinkParams,
+        options: Option<GenerateLinkOptions>,
+    ) -> Result<GeneratedLink, Error> {
+        self.block_on(self.inner.generate_email_action_link(params, options))
+    }
+
+    /// See [`crate::Client::get_user_by_id`].
+    pub fn get_user_by_id(&self, user_id: &str) -> Result<User, Error> {
+        self.block_on(self.inner.get_user_by_id(user_id))
+    }
+
+    /// See [`crate::Client::update_user_by_id`].
+    pub fn update_user_by_id<T: serde::Serialize>(&self, id: &str, user: T) -> Result<User, Error> {
+        self.block_on(self.inner.update_user_by_id(id, user))
+    }
+
+    /// See [`crate::Client::delete_user`].
+    pub fn delete_user(&self, user_id: &str) -> Result<bool, Error> {
+        self.block_on(self.inner.delete_user(user_id))
+    }
+
+    /// See [`crate::Client::list_users`].
+    pub fn list_users(&self, query: Option<ListUsersQuery>) -> Result<UserList, Error> {
+        self.block_on(self.inner.list_users(query))
+    }
+
+    /// See [`crate::Client::list_all_users`]. Each page is fetched when the
+    /// iterator reaches it.
+    pub fn list_all_users(
+        &self,
+        query: ListUsersQuery,
+    ) -> impl Iterator<Item = Result<User, Error>> + '_ {
+        BlockingIter {
+            runtime: &self.runtime,
+            stream: Box::pin(self.inner.list_all_users(query)),
+        }
+    }
+
+    /// See [`crate::Client::export_users`].
+    pub fn export_users(
+        &self,
+        query: ListUsersQuery,
+        format: &ExportFormat,
+        mut writer: impl Write,
+    ) -> Result<usize, Error> {
+        if let Some(header) = export_users::header_line(format) {
+            writer.write_all(header.as_bytes())?;
+        }
+
+        let mut count = 0;
+        for user in self.list_all_users(query) {
+            writer.write_all(export_users::user_line(format, &user?)?.as_bytes())?;
+            count += 1;
+        }
+
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// See [`crate::Client::import_users`].
+    pub fn import_users(
+        &self,
+        users: impl IntoIterator<Item = AdminUserAttributes>,
+        concurrency: usize,
+    ) -> ImportReport {
+        self.block_on(self.inner.import_users(stream::iter(users), concurrency))
+    }
+
+    /// See [`crate::Client::list_audit_log`].
+    pub fn list_audit_log(&self, query: Option<AuditLogQuery>) -> Result<AuditLogPage, Error> {
+        self.block_on(self.inner.list_audit_log(query))
+    }
+
+    /// See [`crate::Client::audit_log`]. Each page is fetched when the
+    /// iterator reaches it.
+    pub fn audit_log(
+        &self,
+        query: AuditLogQuery,
+    ) -> impl Iterator<Item = Result<AuditLogEntry, Error>> + '_ {
+        BlockingIter {
+            runtime: &self.runtime,
+            stream: Box::pin(self.inner.audit_log(query)),
+        }
+    }
+
+    /// See [`crate::Client::list_sso_providers`].
+    pub fn list_sso_providers(&self) -> Result<Vec<SsoProvider>, Error> {
+        self.block_on(self.inner.list_sso_providers())
+    }
+
+    /// See [`crate::Client::create_sso_provider`].
+    pub fn create_sso_provider(
+        &self,
+        attributes: &SsoProviderAttributes,
+    ) -> Result<SsoProvider, Error> {
+        self.block_on(self.inner.create_sso_provider(attributes))
+    }
+
+    /// See [`crate::Client::get_sso_provider`].
+    pub fn get_sso_provider(&self, id: &str) -> Result<SsoProvider, Error> {
+        self.block_on(self.inner.get_sso_provider(id))
+    }
+
+    /// See [`crate::Client::update_sso_provider`].
+    pub fn update_sso_provider(
+        &self,
+        id: &str,
+        attributes: &SsoProviderAttributes,
+    ) -> Result<SsoProvider, Error> {
+        self.block_on(self.inner.update_sso_provider(id, attributes))
+    }
+
+    /// See [`crate::Client::delete_sso_provider`].
+    pub fn delete_sso_provider(&self, id: &str) -> Result<SsoProvider, Error> {
+        self.block_on(self.inner.delete_sso_provider(id))
+    }
+}
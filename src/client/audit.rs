@@ -0,0 +1,192 @@
+use futures_util::Stream;
+use tracing::{debug, error};
+
+use crate::{
+    audit_log::{AuditLogEntry, AuditLogPage},
+    Client, Error,
+};
+
+use super::{
+    handle_gotrue_resp,
+    pagination::{next_page, total_count, walk_pages, Page},
+};
+
+/// The number of entries requested per page when walking the audit log.
+const DEFAULT_PER_PAGE: u32 = 50;
+
+/// Narrows the audit log down to matching entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditLogFilter {
+    /// Entries whose actor's email, phone number or name contains this string.
+    Author(String),
+    /// Entries whose action contains this string, e.g. `login`.
+    Action(String),
+    /// Entries whose log type contains this string, e.g. `account`.
+    Type(String),
+}
+
+/// Query parameters for [`Client::list_audit_log`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLogQuery {
+    /// The page to fetch, starting at 1.
+    pub page: Option<u32>,
+    /// The number of entries per page.
+    pub per_page: Option<u32>,
+    /// Only return entries matching this filter.
+    pub filter: Option<AuditLogFilter>,
+}
+
+impl AuditLogQuery {
+    /// Sets the page to fetch, starting at 1.
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Sets the number of entries per page.
+    pub fn with_per_page(mut self, per_page: u32) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Only returns entries matching `filter`.
+    pub fn with_filter(mut self, filter: AuditLogFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(page) = self.page {
+            pairs.push(("page", page.to_string()));
+        }
+        if let Some(per_page) = self.per_page {
+            pairs.push(("per_page", per_page.to_string()));
+        }
+        if let Some(filter) = &self.filter {
+            let query = match filter {
+                AuditLogFilter::Author(author) => format!("author:{author}"),
+                AuditLogFilter::Action(action) => format!("action:{action}"),
+                AuditLogFilter::Type(log_type) => format!("type:{log_type}"),
+            };
+            pairs.push(("query", query));
+        }
+        pairs
+    }
+}
+
+impl Client {
+    /// Lists audit log entries, newest first, one page at a time.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{AuditLogFilter, AuditLogQuery, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let query = AuditLogQuery::default()
+    ///         .with_filter(AuditLogFilter::Action("login".to_string()));
+    ///     let page = client.list_audit_log(Some(query)).await?;
+    ///
+    ///     for entry in page.entries {
+    ///         println!("{:?} {} from {}", entry.actor.username, entry.action, entry.ip_address);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_audit_log(
+        &self,
+        query: Option<AuditLogQuery>,
+    ) -> Result<AuditLogPage, Error> {
+        let endpoint = format!("{}/admin/audit", self.url);
+        let query = query.unwrap_or_default();
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .get(endpoint)
+            .headers(self.headers.clone())
+            .query(&query.to_pairs());
+        let resp = match self.send_idempotent(request).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        let resp = handle_gotrue_resp(resp).await?;
+
+        let total = total_count(&resp);
+        let next_page = next_page(&resp);
+
+        let entries = match resp.json::<Vec<AuditLogEntry>>().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!(
+                    "could not deserialize the response into audit log entries: {}",
+                    e
+                );
+                return Err(Error::InternalError);
+            }
+        };
+
+        Ok(AuditLogPage {
+            entries,
+            total,
+            next_page,
+        })
+    }
+
+    /// Streams every audit log entry matching the query, newest first, fetching
+    /// pages as they are consumed.
+    ///
+    /// The query's page is where the walk starts, and its `per_page` is used for
+    /// every request (50 if unset).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use go_true_redux::{AuditLogQuery, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let mut entries = Box::pin(client.audit_log(AuditLogQuery::default()));
+    ///     while let Some(entry) = entries.try_next().await? {
+    ///         println!("{} {}", entry.created_at, entry.action);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn audit_log(
+        &self,
+        query: AuditLogQuery,
+    ) -> impl Stream<Item = Result<AuditLogEntry, Error>> + '_ {
+        let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        let first = query.page.unwrap_or(1);
+        let query = query.with_per_page(per_page);
+
+        walk_pages(first, per_page, move |page| {
+            let query = query.clone().with_page(page);
+            async move {
+                let list = self.list_audit_log(Some(query)).await?;
+                Ok(Page {
+                    items: list.entries,
+                    next_page: list.next_page,
+                    total: list.total,
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, error, warn};
+
+use crate::{
+    client::transport::{HttpRequest, HttpResponse, HttpTransport, TransportError},
+    Client, Error,
+};
+
+/// Whether a [`Cassette`] records real exchanges or replays recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Requests go to GoTrue, and every exchange is written to the cassette file.
+    Record,
+    /// Requests are answered from the cassette file, without any network access.
+    Replay,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    /// The path and query of the URL, so cassettes do not depend on the host.
+    path: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    body: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    body: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    values: BTreeMap<String, String>,
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+struct Tape {
+    path: PathBuf,
+    mode: CassetteMode,
+    values: Mutex<BTreeMap<String, String>>,
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+/// Recorded GoTrue exchanges, to pin GoTrue's behaviour and run tests offline.
+///
+/// Set on a client with [`Client::with_cassette`]. In [`CassetteMode::Record`]
+/// every exchange is kept and written to the file when the last clone of the
+/// cassette is dropped, or earlier with [`Cassette::save`]. In
+/// [`CassetteMode::Replay`] each request is answered with the first unplayed
+/// recording of the same method, path and body. JSON bodies are compared as
+/// values, so the order of their keys does not matter. A request that was
+/// never recorded gets a `501 Not Implemented` response, so it fails like a
+/// GoTrue server error and the miss is logged.
+///
+/// # Example
+///
+/// ```no_run
+/// use go_true_redux::{Cassette, Client};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let url = "http://localhost:9998";
+///
+///     let client = Client::new(url).with_cassette(Cassette::record("tests/cassettes/settings.json"));
+///     client.get_settings().await?;
+///     drop(client);
+///
+///     // Later, without GoTrue running:
+///     let client = Client::new(url).with_cassette(Cassette::replay("tests/cassettes/settings.json")?);
+///     client.get_settings().await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    tape: Arc<Tape>,
+}
+
+/// Keeps JSON bodies readable in the cassette file.
+fn body_to_value(bytes: &[u8]) -> Value {
+    if bytes.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+fn value_to_body(value: &Value) -> Vec<u8> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::String(text) => text.clone().into_bytes(),
+        value => value.to_string().into_bytes(),
+    }
+}
+
+fn path_of(request: &HttpRequest) -> String {
+    match request.uri().path_and_query() {
+        Some(path) => path.to_string(),
+        None => "/".to_owned(),
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> HttpResponse {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        match builder.body(value_to_body(&self.body)) {
+            Ok(response) => response,
+            Err(e) => {
+                error!("could not replay the recorded response: {}", e);
+                let mut response = http::Response::new(Vec::new());
+                *response.status_mut() = http::StatusCode::INTERNAL_SERVER_ERROR;
+                response
+            }
+        }
+    }
+}
+
+impl Cassette {
+    /// A cassette recording to `path`, replacing the file when saved.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Cassette::new(path.into(), CassetteMode::Record, CassetteFile::default())
+    }
+
+    /// A cassette replaying the exchanges recorded in `path`.
+    ///
+    /// Fails with [`Error::Io`] if the file cannot be read or is not a cassette.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let file: CassetteFile = serde_json::from_slice(&fs::read(&path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Cassette::new(path, CassetteMode::Replay, file))
+    }
+
+    fn new(path: PathBuf, mode: CassetteMode, file: CassetteFile) -> Self {
+        let interactions = file.interactions.into_iter().map(|i| (i, false)).collect();
+        Cassette {
+            tape: Arc::new(Tape {
+                path,
+                mode,
+                values: Mutex::new(file.values),
+                interactions: Mutex::new(interactions),
+            }),
+        }
+    }
+
+    /// Whether the cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.tape.mode
+    }
+
+    /// The file the cassette is recorded to or replayed from.
+    pub fn path(&self) -> &Path {
+        &self.tape.path
+    }
+
+    /// Writes the recorded exchanges to the cassette file, creating its directory if needed.
+    pub fn save(&self) -> Result<(), Error> {
+        self.tape.save()
+    }
+
+    /// A value that must differ between recordings but stay the same when
+    /// replaying, such as the email of a user signed up by a test.
+    ///
+    /// When recording, the value is made by `generate` and saved with the
+    /// exchanges. When replaying, the saved value is returned, and `generate`
+    /// is only called if the cassette has none for `key`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::Cassette;
+    ///
+    /// let cassette = Cassette::record("tests/cassettes/sign_up.json");
+    /// let email = cassette.value("email", || format!("{}@example.com", std::process::id()));
+    /// ```
+    pub fn value(&self, key: &str, generate: impl FnOnce() -> String) -> String {
+        let mut values = self.tape.values.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(value) = values.get(key) {
+            return value.clone();
+        }
+
+        if self.tape.mode == CassetteMode::Replay {
+            warn!("no recorded value for {}", key);
+        }
+        let value = generate();
+        values.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    /// Answers the request from the recordings.
+    pub(crate) fn play(&self, request: &HttpRequest) -> HttpResponse {
+        let method = request.method().as_str();
+        let path = path_of(request);
+        let body = body_to_value(request.body());
+
+        let mut interactions = self.tape.lock();
+        let recorded = interactions.iter_mut().find(|(interaction, played)| {
+            !played
+                && interaction.request.method == method
+                && interaction.request.path == path
+                && interaction.request.body == body
+        });
+
+        match recorded {
+            Some((interaction, played)) => {
+                *played = true;
+                debug!("replaying {} {}", method, path);
+                interaction.response.to_response()
+            }
+            None => {
+                warn!("no recorded exchange for {} {}", method, path);
+                RecordedResponse {
+                    status: 501,
+                    headers: BTreeMap::from([(
+                        "content-type".to_owned(),
+                        "application/json".to_owned(),
+                    )]),
+                    body: json!({
+                        "code": 501,
+                        "msg": format!("no recorded exchange for {method} {path}"),
+                    }),
+                }
+                .to_response()
+            }
+        }
+    }
+
+    /// Sends the request to GoTrue and keeps the exchange.
+    pub(crate) async fn record_exchange(
+        &self,
+        transport: &dyn HttpTransport,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, TransportError> {
+        let recorded_request = RecordedRequest {
+            method: request.method().as_str().to_owned(),
+            path: path_of(&request),
+            body: body_to_value(request.body()),
+        };
+
+        let resp = transport.send(request).await?;
+        let headers = resp
+            .headers()
+            .iter()
+            .filter(|(name, _)| *name != "transfer-encoding" && *name != "connection")
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+
+        let recorded_response = RecordedResponse {
+            status: resp.status().as_u16(),
+            headers,
+            body: body_to_value(resp.body()),
+        };
+        let resp = recorded_response.to_response();
+
+        self.tape.lock().push((
+            Interaction {
+                request: recorded_request,
+                response: recorded_response,
+            },
+            true,
+        ));
+
+        Ok(resp)
+    }
+}
+
+impl Tape {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(Interaction, bool)>> {
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        let file = CassetteFile {
+            values: self
+                .values
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            interactions: self.lock().iter().map(|(i, _)| i.clone()).collect(),
+        };
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, json)?;
+
+        debug!("saved cassette {}", self.path.display());
+        Ok(())
+    }
+}
+
+impl Drop for Tape {
+    fn drop(&mut self) {
+        if self.mode == CassetteMode::Record {
+            if let Err(e) = self.save() {
+                error!("could not save cassette {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Records GoTrue exchanges to, or replays them from, a [`Cassette`].
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+}
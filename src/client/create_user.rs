@@ -1,8 +1,8 @@
 use tracing::{debug, error};
 
-use crate::{user::User, Client, Error};
+use crate::{admin_user_attributes::AdminUserAttributes, user::User, Client, Error};
 
-use super::handle_gotrue_resp_status;
+use super::handle_gotrue_resp;
 
 impl Client {
     /// Creates a user
@@ -24,7 +24,7 @@ impl Client {
     ///         email_confirmed: None,
     ///         phone_confirmed: None,
     ///     };
-
+    ///
     ///     client.create_user(user).await?;
     ///
     ///     Ok(())
@@ -43,11 +43,69 @@ impl Client {
 
         debug!("calling {}", endpoint);
         let resp = match self
-            .client
-            .post(endpoint)
-            .headers(self.headers.clone())
-            .json(&json)
-            .send()
+            .execute(
+                self.post(endpoint)
+                    .headers(self.headers.clone())
+                    .json(&json),
+            )
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        let resp = handle_gotrue_resp(resp).await?;
+
+        let user = match resp.json::<User>().await {
+            Ok(user) => user,
+            Err(e) => {
+                error!("could not deserialize the response into a user: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        Ok(user)
+    }
+
+    /// Creates a user as an admin.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{AdminUserAttributes, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let attributes = AdminUserAttributes {
+    ///         email: Some("createemail@example.com".to_string()),
+    ///         password: Some("Abcd1234!".to_string()),
+    ///         email_confirm: Some(true),
+    ///         ..AdminUserAttributes::default()
+    ///     };
+    ///
+    ///     let user = client.admin_create_user(&attributes).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn admin_create_user(&self, attributes: &AdminUserAttributes) -> Result<User, Error> {
+        let endpoint = format!("{}/admin/users", self.url);
+
+        debug!("calling {}", endpoint);
+        let resp = match self
+            .execute(
+                self.post(endpoint)
+                    .headers(self.headers.clone())
+                    .json(attributes),
+            )
             .await
         {
             Ok(resp) => resp,
@@ -57,10 +115,7 @@ impl Client {
             }
         };
 
-        if let Err(e) = handle_gotrue_resp_status(resp.status()) {
-            error!("gotrue returned an error status: {}", resp.status());
-            return Err(e);
-        }
+        let resp = handle_gotrue_resp(resp).await?;
 
         let user = match resp.json::<User>().await {
             Ok(user) => user,
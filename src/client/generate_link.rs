@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::{user::User, Client, Error};
+
+use super::gotrue_json;
+
+/// The kind of link to generate with [`Client::generate_email_action_link`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateLinkParams {
+    /// Confirms the signup of a new user, creating them.
+    Signup {
+        /// The email of the user.
+        email: String,
+        /// The password of the user.
+        password: String,
+    },
+    /// Invites a new user, creating them.
+    Invite {
+        /// The email of the user.
+        email: String,
+    },
+    /// Signs an existing user in.
+    MagicLink {
+        /// The email of the user.
+        email: String,
+    },
+    /// Lets an existing user set a new password.
+    Recovery {
+        /// The email of the user.
+        email: String,
+    },
+    /// Confirms an email change from the current address.
+    EmailChangeCurrent {
+        /// The current email of the user.
+        email: String,
+        /// The email to change to.
+        new_email: String,
+    },
+    /// Confirms an email change from the new address.
+    EmailChangeNew {
+        /// The current email of the user.
+        email: String,
+        /// The email to change to.
+        new_email: String,
+    },
+}
+
+impl GenerateLinkParams {
+    fn link_type(&self) -> &'static str {
+        match self {
+            GenerateLinkParams::Signup { .. } => "signup",
+            GenerateLinkParams::Invite { .. } => "invite",
+            GenerateLinkParams::MagicLink { .. } => "magiclink",
+            GenerateLinkParams::Recovery { .. } => "recovery",
+            GenerateLinkParams::EmailChangeCurrent { .. } => "email_change_current",
+            GenerateLinkParams::EmailChangeNew { .. } => "email_change_new",
+        }
+    }
+
+    fn to_body(&self) -> Value {
+        let mut body = json!({ "type": self.link_type() });
+        match self {
+            GenerateLinkParams::Signup { email, password } => {
+                body["email"] = json!(email);
+                body["password"] = json!(password);
+            }
+            GenerateLinkParams::Invite { email }
+            | GenerateLinkParams::MagicLink { email }
+            | GenerateLinkParams::Recovery { email } => {
+                body["email"] = json!(email);
+            }
+            GenerateLinkParams::EmailChangeCurrent { email, new_email }
+            | GenerateLinkParams::EmailChangeNew { email, new_email } => {
+                body["email"] = json!(email);
+                body["new_email"] = json!(new_email);
+            }
+        }
+        body
+    }
+}
+
+/// Options for [`Client::generate_email_action_link`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenerateLinkOptions {
+    /// Where to send the user after they follow the link. Defaults to the site URL.
+    pub redirect_to: Option<String>,
+    /// The user metadata of a user created by a signup or invite link.
+    pub data: Option<Value>,
+}
+
+/// A link generated by [`Client::generate_email_action_link`], with the user it is for.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GeneratedLink {
+    /// The link to send to the user.
+    pub action_link: String,
+    /// The one-time code matching the link, to verify with [`Client::verify_otp`].
+    #[serde(default)]
+    pub email_otp: Option<String>,
+    /// The hashed token in the link.
+    #[serde(default)]
+    pub hashed_token: Option<String>,
+    /// The kind of token in the link, e.g. `magiclink`.
+    #[serde(default)]
+    pub verification_type: Option<String>,
+    /// Where the user is sent after following the link.
+    #[serde(default)]
+    pub redirect_to: Option<String>,
+    /// The user the link is for.
+    #[serde(flatten)]
+    pub user: User,
+}
+
+impl Client {
+    /// Generates the link GoTrue would email for an action, without sending it,
+    /// to deliver it another way.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, GenerateLinkParams};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let params = GenerateLinkParams::Recovery {
+    ///         email: "email@example.com".to_string(),
+    ///     };
+    ///     let link = client.generate_email_action_link(&params, None).await?;
+    ///
+    ///     println!("{}", link.action_link);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn generate_email_action_link(
+        &self,
+        params: &GenerateLinkParams,
+        options: Option<GenerateLinkOptions>,
+    ) -> Result<GeneratedLink, Error> {
+        let endpoint = format!("{}/admin/generate_link", self.url);
+        let options = options.unwrap_or_default();
+
+        let mut body = params.to_body();
+        if let Some(redirect_to) = options.redirect_to {
+            body["redirect_to"] = json!(redirect_to);
+        }
+        if let Some(data) = options.data {
+            body["data"] = data;
+        }
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .post(endpoint)
+            .headers(self.headers.clone())
+            .json(&body);
+
+        gotrue_json(self.execute(request).await).await
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, timeout, Instant};
+use tracing::{debug, warn};
+
+use crate::{client::gotrue_json, Client, Error};
+
+/// The time between two checks of [`Client::wait_until_ready`].
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What a GoTrue instance reports about itself on `/health`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Health {
+    /// The version of GoTrue.
+    pub version: String,
+    /// The name of the service.
+    pub name: String,
+    /// The description of the service.
+    pub description: String,
+}
+
+impl Client {
+    /// Checks that GoTrue is up, returning its version and name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let health = client.health().await?;
+    ///     println!("{} {}", health.name, health.version);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn health(&self) -> Result<Health, Error> {
+        let endpoint = format!("{}/health", self.url);
+
+        debug!("calling {}", endpoint);
+        let request = self.get(endpoint).headers(self.headers.clone());
+        gotrue_json(self.execute(request).await).await
+    }
+
+    /// Waits until GoTrue answers [`Client::health`], for deploy scripts and
+    /// tests starting alongside it.
+    ///
+    /// Checks every 250ms. Fails with [`Error::NotReady`] if GoTrue is still
+    /// not up after `wait`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use go_true_redux::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     client.wait_until_ready(Duration::from_secs(30)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_until_ready(&self, wait: Duration) -> Result<Health, Error> {
+        let deadline = Instant::now() + wait;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match timeout(remaining, self.health()).await {
+                Ok(Ok(health)) => return Ok(health),
+                Ok(Err(e)) => debug!("gotrue is not ready yet: {}", e),
+                Err(_) => {}
+            }
+
+            if Instant::now() + READY_POLL_INTERVAL >= deadline {
+                warn!("gotrue was not ready after {:?}", wait);
+                return Err(Error::NotReady { waited: wait });
+            }
+            sleep(READY_POLL_INTERVAL).await;
+        }
+    }
+}
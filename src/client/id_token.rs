@@ -0,0 +1,93 @@
+use serde::Serialize;
+use tracing::{debug, error};
+
+use crate::{session::Session, Client, Error};
+
+use super::handle_gotrue_resp;
+
+/// An ID token obtained natively from an identity provider, for [`Client::sign_in_with_id_token`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IdTokenCredentials {
+    /// The provider that issued the token, e.g. `google` or `apple`.
+    pub provider: String,
+    /// The OIDC ID token issued by the provider.
+    pub id_token: String,
+    /// The access token issued alongside the ID token, if the ID token has an
+    /// `at_hash` claim to check it against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    /// The raw nonce, if a hash of it was passed to the provider when signing in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+impl Client {
+    /// Signs a user in with an ID token obtained natively, such as from Google
+    /// One Tap or Sign in with Apple, signing them up if needed.
+    ///
+    /// This complements the redirect-based [`Client::get_url_for_provider`].
+    /// Fails with [`Error::WrongToken`] if GoTrue rejects the ID token.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, IdTokenCredentials};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let credentials = IdTokenCredentials {
+    ///         provider: "apple".to_string(),
+    ///         id_token: "eyJhbGciOi...".to_string(),
+    ///         nonce: Some("raw-nonce".to_string()),
+    ///         ..IdTokenCredentials::default()
+    ///     };
+    ///     let session = client.sign_in_with_id_token(&credentials).await?;
+    ///
+    ///     println!("{}", session.user.email);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sign_in_with_id_token(
+        &self,
+        credentials: &IdTokenCredentials,
+    ) -> Result<Session, Error> {
+        let endpoint = format!("{}/token?grant_type=id_token", self.url);
+
+        debug!("calling {}", endpoint);
+        let resp = match self
+            .execute(
+                self.post(endpoint)
+                    .headers(self.headers.clone())
+                    .json(credentials),
+            )
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        let resp = match handle_gotrue_resp(resp).await {
+            Ok(resp) => resp,
+            Err(Error::Api {
+                status: 400,
+                message,
+            }) if message.to_lowercase().contains("id token") => return Err(Error::WrongToken),
+            Err(e) => return Err(e),
+        };
+
+        match resp.json::<Session>().await {
+            Ok(session) => Ok(session),
+            Err(e) => {
+                error!("could not deserialize the response into a session: {}", e);
+                Err(Error::InternalError)
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use futures_util::{Stream, StreamExt};
+use tracing::{debug, warn};
+
+use crate::{admin_user_attributes::AdminUserAttributes, user::User, Client, Error};
+
+/// What happened to a single user during [`Client::import_users`].
+#[derive(Debug)]
+pub enum ImportOutcome {
+    /// The user was created.
+    Created(Box<User>),
+    /// A user with the same email or phone number already exists.
+    Skipped,
+    /// GoTrue refused to create the user.
+    Failed(Error),
+}
+
+/// The result of importing a single user.
+#[derive(Debug)]
+pub struct ImportRecord {
+    /// The position of the user in the imported stream, starting at 0.
+    pub index: usize,
+    /// The email of the imported user, if any.
+    pub email: Option<String>,
+    /// The phone number of the imported user, if any.
+    pub phone: Option<String>,
+    /// What happened to the user.
+    pub outcome: ImportOutcome,
+}
+
+/// The per-user results of [`Client::import_users`], in input order.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// One record per imported user.
+    pub records: Vec<ImportRecord>,
+}
+
+impl ImportReport {
+    /// The users that were created.
+    pub fn created(&self) -> impl Iterator<Item = &User> {
+        self.records
+            .iter()
+            .filter_map(|record| match &record.outcome {
+                ImportOutcome::Created(user) => Some(user.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// The records skipped because the user already existed.
+    pub fn skipped(&self) -> impl Iterator<Item = &ImportRecord> {
+        self.records
+            .iter()
+            .filter(|record| matches!(record.outcome, ImportOutcome::Skipped))
+    }
+
+    /// The records that could not be imported, with the reason why.
+    pub fn failed(&self) -> impl Iterator<Item = (&ImportRecord, &Error)> {
+        self.records
+            .iter()
+            .filter_map(|record| match &record.outcome {
+                ImportOutcome::Failed(e) => Some((record, e)),
+                _ => None,
+            })
+    }
+}
+
+impl Client {
+    /// Creates every user of the stream, running at most `concurrency` requests at once.
+    ///
+    /// Users that already exist are skipped rather than failed, so an
+    /// interrupted import can simply be run again. Pre-hashed bcrypt passwords
+    /// can be imported through [`AdminUserAttributes::password_hash`].
+    ///
+    /// Every record is kept in the returned report. Use
+    /// [`Client::import_users_stream`] to handle records as they complete
+    /// instead when importing more users than fit in memory.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream;
+    /// use go_true_redux::{AdminUserAttributes, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let users = vec![AdminUserAttributes {
+    ///         email: Some("legacy@example.com".to_string()),
+    ///         password_hash: Some("$2a$10$...".to_string()),
+    ///         email_confirm: Some(true),
+    ///         ..AdminUserAttributes::default()
+    ///     }];
+    ///
+    ///     let report = client.import_users(stream::iter(users), 8).await;
+    ///     for (record, error) in report.failed() {
+    ///         println!("{:?} failed: {}", record.email, error);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn import_users(
+        &self,
+        users: impl Stream<Item = AdminUserAttributes>,
+        concurrency: usize,
+    ) -> ImportReport {
+        let mut records: Vec<ImportRecord> =
+            self.import_users_stream(users, concurrency).collect().await;
+
+        records.sort_by_key(|record| record.index);
+
+        ImportReport { records }
+    }
+
+    /// Like [`Client::import_users`], but yields each record as soon as it completes.
+    ///
+    /// Records come in completion order rather than input order; use
+    /// [`ImportRecord::index`] to match them with their input.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::{stream, StreamExt};
+    /// use go_true_redux::{AdminUserAttributes, Client, ImportOutcome};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let users = (0..100_000).map(|i| AdminUserAttributes {
+    ///         email: Some(format!("legacy{i}@example.com")),
+    ///         ..AdminUserAttributes::default()
+    ///     });
+    ///
+    ///     let mut records = Box::pin(client.import_users_stream(stream::iter(users), 8));
+    ///     while let Some(record) = records.next().await {
+    ///         if let ImportOutcome::Failed(e) = record.outcome {
+    ///             println!("{:?} failed: {}", record.email, e);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn import_users_stream<'a>(
+        &'a self,
+        users: impl Stream<Item = AdminUserAttributes> + 'a,
+        concurrency: usize,
+    ) -> impl Stream<Item = ImportRecord> + 'a {
+        users
+            .enumerate()
+            .map(move |(index, attributes)| async move {
+                let outcome = match self.admin_create_user(&attributes).await {
+                    Ok(user) => ImportOutcome::Created(Box::new(user)),
+                    Err(Error::AlreadySignedUp) => {
+                        debug!("user {} already exists, skipping", index);
+                        ImportOutcome::Skipped
+                    }
+                    Err(e) => {
+                        warn!("could not import user {}: {}", index, e);
+                        ImportOutcome::Failed(e)
+                    }
+                };
+
+                ImportRecord {
+                    index,
+                    email: attributes.email,
+                    phone: attributes.phone,
+                    outcome,
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
+}
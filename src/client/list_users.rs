@@ -0,0 +1,199 @@
+use futures_util::Stream;
+use tracing::{debug, error};
+
+use crate::{user::User, user_list::UserList, Client, Error};
+
+use super::{
+    handle_gotrue_resp,
+    pagination::{next_page, total_count, walk_pages, Page},
+};
+
+/// The number of users requested per page when walking all users.
+const DEFAULT_PER_PAGE: u32 = 50;
+
+/// The direction to sort users in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    /// Oldest or smallest first.
+    Ascending,
+    /// Newest or largest first.
+    Descending,
+}
+
+/// Query parameters for [`Client::list_users`].
+///
+/// # Example
+///
+/// ```
+/// use go_true_redux::{ListUsersQuery, SortDirection};
+///
+/// let query = ListUsersQuery::default()
+///     .with_page(2)
+///     .with_per_page(50)
+///     .with_filter("example.com")
+///     .with_sort("created_at", SortDirection::Descending);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListUsersQuery {
+    /// The page to fetch, starting at 1.
+    pub page: Option<u32>,
+    /// The number of users per page.
+    pub per_page: Option<u32>,
+    /// Only return users whose email or name contains this string.
+    pub filter: Option<String>,
+    /// The field and direction to sort by. GoTrue supports sorting by `created_at`.
+    pub sort: Option<(String, SortDirection)>,
+}
+
+impl ListUsersQuery {
+    /// Sets the page to fetch, starting at 1.
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Sets the number of users per page.
+    pub fn with_per_page(mut self, per_page: u32) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Only returns users whose email or name contains `filter`.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Sorts users by `field` in the given direction.
+    pub fn with_sort(mut self, field: impl Into<String>, direction: SortDirection) -> Self {
+        self.sort = Some((field.into(), direction));
+        self
+    }
+
+    fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(page) = self.page {
+            pairs.push(("page", page.to_string()));
+        }
+        if let Some(per_page) = self.per_page {
+            pairs.push(("per_page", per_page.to_string()));
+        }
+        if let Some(filter) = &self.filter {
+            pairs.push(("filter", filter.clone()));
+        }
+        if let Some((field, direction)) = &self.sort {
+            let direction = match direction {
+                SortDirection::Ascending => "asc",
+                SortDirection::Descending => "desc",
+            };
+            pairs.push(("sort", format!("{field} {direction}")));
+        }
+        pairs
+    }
+}
+
+impl Client {
+    /// Lists users, one page at a time.
+    ///
+    /// The returned [`UserList`] carries the total number of users and the next
+    /// page, if GoTrue reported them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, ListUsersQuery};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let query = ListUsersQuery::default().with_page(1).with_per_page(50);
+    ///     let users = client.list_users(Some(query)).await?;
+    ///
+    ///     println!("{} users in total", users.total.unwrap_or_default());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_users(&self, query: Option<ListUsersQuery>) -> Result<UserList, Error> {
+        let endpoint = format!("{}/admin/users", self.url);
+        let query = query.unwrap_or_default();
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .get(endpoint)
+            .headers(self.headers.clone())
+            .query(&query.to_pairs());
+        let resp = match self.send_idempotent(request).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        let resp = handle_gotrue_resp(resp).await?;
+
+        let total = total_count(&resp);
+        let next_page = next_page(&resp);
+
+        let mut users = match resp.json::<UserList>().await {
+            Ok(users) => users,
+            Err(e) => {
+                error!("could not deserialize the response into a user list: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        users.total = total;
+        users.next_page = next_page;
+
+        Ok(users)
+    }
+
+    /// Streams every user matching the query, fetching pages as they are consumed.
+    ///
+    /// The query's page is where the walk starts, and its `per_page` is used for
+    /// every request (50 if unset).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use go_true_redux::{Client, ListUsersQuery};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let mut users = Box::pin(client.list_all_users(ListUsersQuery::default()));
+    ///     while let Some(user) = users.try_next().await? {
+    ///         println!("{}", user.email);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_all_users(
+        &self,
+        query: ListUsersQuery,
+    ) -> impl Stream<Item = Result<User, Error>> + '_ {
+        let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        let first = query.page.unwrap_or(1);
+        let query = query.with_per_page(per_page);
+
+        walk_pages(first, per_page, move |page| {
+            let query = query.clone().with_page(page);
+            async move {
+                let list = self.list_users(Some(query)).await?;
+                Ok(Page {
+                    items: list.users,
+                    next_page: list.next_page,
+                    total: list.total,
+                })
+            }
+        })
+    }
+}
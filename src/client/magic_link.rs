@@ -0,0 +1,114 @@
+use serde_json::{json, Value};
+use tracing::{debug, error};
+
+use crate::{client::otp::OtpOptions, Client, EmailOrPhone, Error};
+
+use super::handle_gotrue_resp;
+
+/// How the user completes an email sign-in started with [`Client::sign_in_with_magic_link`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmailOtpDelivery {
+    /// The user clicks the link of the email, which redirects to `redirect_to`
+    /// with a session in its fragment.
+    #[default]
+    Link,
+    /// The user types the 6-digit code of the email back into the app, which
+    /// verifies it with [`crate::VerifyParams::MagicLink`].
+    ///
+    /// This sends the same email as [`Client::send_otp`]: GoTrue chooses
+    /// between a link and a code only through its magic link email template,
+    /// which must include `{{ .Token }}` for the user to get a code. The
+    /// delivery only changes which endpoint is called.
+    Code,
+}
+
+/// Options for [`Client::sign_in_with_magic_link`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MagicLinkOptions {
+    /// Whether the user signs in through the link or by typing a code.
+    pub delivery: EmailOtpDelivery,
+    /// Where to send the user after they click the link. Defaults to the site URL.
+    pub redirect_to: Option<String>,
+    /// Metadata for the user, if they are signed up by this call.
+    pub data: Option<Value>,
+    /// The captcha token, if captcha protection is enabled.
+    pub captcha_token: Option<String>,
+}
+
+impl Client {
+    /// Signs a user in without a password by emailing them a magic link or a code.
+    ///
+    /// Users that do not exist yet are signed up. Fails with
+    /// [`Error::RateLimited`] if an email was sent to this address too recently.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailOtpDelivery, MagicLinkOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let options = MagicLinkOptions {
+    ///         delivery: EmailOtpDelivery::Link,
+    ///         redirect_to: Some("https://example.com/welcome".to_string()),
+    ///         ..MagicLinkOptions::default()
+    ///     };
+    ///     client.sign_in_with_magic_link("email@example.com", &options).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sign_in_with_magic_link(
+        &self,
+        email: &str,
+        options: &MagicLinkOptions,
+    ) -> Result<bool, Error> {
+        let address = EmailOrPhone::Email(email.to_owned());
+        self.check_sign_in(&address).await?;
+
+        if options.delivery == EmailOtpDelivery::Code {
+            let options = OtpOptions {
+                data: options.data.clone(),
+                email_redirect_to: options.redirect_to.clone(),
+                captcha_token: options.captcha_token.clone(),
+                ..OtpOptions::default()
+            };
+            return self.send_otp(address, Some(options)).await;
+        }
+
+        let endpoint = format!("{}/magiclink", self.url);
+
+        self.check_cooldown(&address)?;
+
+        let mut body = json!({ "email": email });
+        if let Some(data) = &options.data {
+            body["data"] = data.clone();
+        }
+        if let Some(captcha_token) = &options.captcha_token {
+            body["gotrue_meta_security"] = json!({ "captcha_token": captcha_token });
+        }
+
+        let mut request = self
+            .post(endpoint.as_str())
+            .headers(self.headers.clone())
+            .json(&body);
+        if let Some(redirect_to) = &options.redirect_to {
+            request = request.query(&[("redirect_to", redirect_to)]);
+        }
+
+        debug!("calling {}", endpoint);
+        let result = match self.execute(request).await {
+            Ok(resp) => handle_gotrue_resp(resp).await.map(|_| true),
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                Err(Error::InternalError)
+            }
+        };
+
+        self.record_cooldown(&address, &result);
+        result
+    }
+}
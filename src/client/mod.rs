@@ -1,15 +1,50 @@
+pub mod audit;
+pub mod cassette;
 pub mod create_user;
+pub mod export_users;
+pub mod generate_link;
+pub mod health;
+pub mod id_token;
+pub mod import_users;
+pub mod list_users;
+pub mod magic_link;
+pub mod otp;
+mod pagination;
+pub mod pending_change;
+pub mod preflight;
+pub mod rate_limit;
+pub mod reauthenticate;
+mod request;
+pub mod resend;
+pub mod retry;
 pub mod settings;
+pub mod sign_out;
+pub mod sso;
+pub mod transport;
+pub mod verify;
 
-use reqwest::{
+use std::sync::Arc;
+
+use http::{
     header::{HeaderMap, HeaderValue, IntoHeaderName},
     StatusCode,
 };
+use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::json;
-
+use tracing::{debug, error};
+
+use self::{
+    cassette::Cassette,
+    preflight::SettingsCache,
+    rate_limit::RateLimitTracker,
+    request::Response,
+    retry::RetryPolicy,
+    sign_out::SignOutScope,
+    transport::{HttpTransport, TransportError},
+};
 use crate::{
-    session::Session, user::User, user_attributes::UserAttributes, user_list::UserList,
-    user_update::UserUpdate, Error,
+    client::settings::Provider, session::Session, user::User, user_attributes::UserAttributes,
+    Error,
 };
 
 ///
@@ -19,11 +54,15 @@ use crate::{
 pub struct Client {
     url: String,
     headers: HeaderMap,
-    client: reqwest::Client,
+    transport: Arc<dyn HttpTransport>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit_tracker: Option<RateLimitTracker>,
+    cassette: Option<Cassette>,
+    settings_cache: Option<SettingsCache>,
 }
 
 /// Represent either an email or phone number for OTP.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EmailOrPhone {
     /// Email
     Email(String),
@@ -41,11 +80,23 @@ impl Client {
     ///
     /// let client = Api::new("http://your.gotrue.endpoint");
     /// ```
+    #[cfg(feature = "reqwest-transport")]
     pub fn new(url: &str) -> Client {
+        Client::with_transport(url, transport::ReqwestTransport::new())
+    }
+
+    /// Creates a GoTrue API client sending its requests with the given transport.
+    ///
+    /// See [`HttpTransport`] for an example.
+    pub fn with_transport(url: &str, transport: impl HttpTransport + 'static) -> Client {
         Client {
             url: url.to_owned(),
             headers: HeaderMap::new(),
-            client: reqwest::Client::new(),
+            transport: Arc::new(transport),
+            retry_policy: None,
+            rate_limit_tracker: None,
+            cassette: None,
+            settings_cache: None,
         }
     }
 
@@ -53,21 +104,11 @@ impl Client {
     NEW API
     */
 
-    /// Creates a user based on the user_id specified.
-    pub async fn admin_create_user(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
     /// Updates a user based on the user_id specified.
     pub async fn admin_update_user(&self) -> anyhow::Result<()> {
         Ok(())
     }
 
-    /// Generates a corresponding email action link based on the type specified.
-    pub async fn generate_email_action_link(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
     /// Register a new user with an email and password.
     pub async fn signup(&self) -> anyhow::Result<()> {
         Ok(())
@@ -76,21 +117,10 @@ impl Client {
     /// Invites a new user with an email.
     ///
     /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header:
-
     pub async fn invite(&self) -> anyhow::Result<()> {
         Ok(())
     }
 
-    /// Verify a registration or a password recovery.
-    pub async fn verify_registration_or_recovery(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    ///  Delivers a magiclink or sms otp to the user depending on whether the request body contains an "email" or "phone" key.
-    pub async fn send_new_otp(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
     /// Send a password recovery mail to the user based on email address.
     pub async fn send_password_recovery(&self) -> anyhow::Result<()> {
         Ok(())
@@ -106,20 +136,6 @@ impl Client {
         Ok(())
     }
 
-    /// Sends a nonce to the user's email (preferred) or phone.
-    /// This endpoint requires the user to be logged in / authenticated first.
-    /// The user needs to have either an email or phone number for the nonce to be sent successfully.
-    pub async fn reauth(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    /// Logout a user (Requires authentication).
-    /// This will revoke all refresh tokens for the user.
-    /// Remember that the JWT tokens will still be valid for stateless auth until they expires.
-    pub async fn sign_out_new(&self) -> anyhow::Result<()> {
-        Ok(())
-    }
-
     /// Get access_token from external oauth provider
     pub async fn external_authorize(&self) -> anyhow::Result<()> {
         Ok(())
@@ -156,6 +172,17 @@ impl Client {
         self
     }
 
+    /// The client headers with the user's JWT as bearer token.
+    fn authorized_headers(&self, jwt: &str) -> HeaderMap {
+        let mut headers: HeaderMap = self.headers.clone();
+        let bearer = format!("Bearer {jwt}");
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(bearer.as_ref()).expect("Invalid header value."),
+        );
+        headers
+    }
+
     /// Signs up for a new account
     ///
     /// # Example
@@ -179,9 +206,11 @@ impl Client {
         &self,
         email_or_phone: EmailOrPhone,
         password: &str,
-    ) -> Result<Session, reqwest::Error> {
+    ) -> Result<Session, Error> {
         let endpoint = format!("{}/signup", self.url);
 
+        self.check_sign_up(&email_or_phone).await?;
+
         let body = match email_or_phone {
             EmailOrPhone::Email(email) => json!({
                 "email": email,
@@ -193,16 +222,11 @@ impl Client {
             }),
         };
 
-        let response: Session = self
-            .client
+        let request = self
             .post(endpoint)
             .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<Session>()
-            .await?;
+            .json(&body);
+        let response: Session = gotrue_json(self.execute(request).await).await?;
 
         Ok(response)
     }
@@ -231,7 +255,7 @@ impl Client {
         &self,
         email_or_phone: EmailOrPhone,
         password: &str,
-    ) -> Result<Session, reqwest::Error> {
+    ) -> Result<Session, Error> {
         let query_string = String::from("?grant_type=password");
 
         let endpoint = format!("{}/token{}", self.url, query_string);
@@ -247,91 +271,18 @@ impl Client {
             }),
         };
 
-        let response: Session = self
-            .client
+        let request = self
             .post(endpoint)
             .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<Session>()
-            .await?;
+            .json(&body);
+        let response: Session = gotrue_json(self.execute(request).await).await?;
 
         Ok(response)
     }
 
-    /// Sends an OTP Code and creates user if it does not exist
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use go_true::{Api, EmailOrPhone};
-    ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let url = "http://localhost:9998";
-    ///     let mut client = Api::new(url);
-    ///
-    ///     let email = "email@example.com".to_string();
-    ///
-    ///     let result = client.send_otp(EmailOrPhone::Email(email), None).await;
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn send_otp(
-        &self,
-        email_or_phone: EmailOrPhone,
-        should_create_user: Option<bool>,
-    ) -> Result<bool, reqwest::Error> {
-        let endpoint = format!("{}/otp", self.url);
-
-        let body = match email_or_phone {
-            EmailOrPhone::Email(email) => json!({
-                "email": email,
-                "should_create_user": Some(should_create_user)
-            }),
-            EmailOrPhone::Phone(phone) => json!({
-                "phone": phone,
-                "should_create_user": Some(should_create_user)
-            }),
-        };
-
-        self.client
-            .post(endpoint)
-            .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?;
-
-        Ok(true)
-    }
-
-    /// Verifies a phone signup or sms otp.
+    /// Signs the current user out of every device
     ///
-    /// # Example
-    ///
-    /// ```
-    /// // TODO
-    /// ```
-    pub async fn verify_otp<T: serde::Serialize>(&self, params: T) -> Result<bool, reqwest::Error> {
-        let endpoint = format!("{}/verify", self.url);
-
-        let body = serde_json::to_value(&params).unwrap();
-
-        self.client
-            .post(endpoint)
-            .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?;
-
-        Ok(true)
-    }
-
-    /// Signs the current user out
+    /// See [`Client::sign_out_with_scope`] to choose which sessions are ended.
     ///
     /// # Example
     ///
@@ -353,55 +304,58 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn sign_out(&self, access_token: &str) -> Result<bool, reqwest::Error> {
-        let endpoint = format!("{}/logout", self.url);
-
-        let mut headers: HeaderMap = self.headers.clone();
-        let bearer = format!("Bearer {access_token}");
-        headers.insert(
-            "Authorization",
-            HeaderValue::from_str(bearer.as_ref()).expect("Invalid header value."),
-        );
-
-        self.client
-            .post(endpoint)
-            .headers(headers)
-            .send()
-            .await?
-            .error_for_status()?;
-
-        Ok(true)
+    pub async fn sign_out(&self, access_token: &str) -> Result<(), Error> {
+        self.sign_out_with_scope(access_token, SignOutScope::Global)
+            .await
     }
 
     /// Sends password recovery email
     ///
+    /// Fails with [`Error::RateLimited`] if a recovery email was sent to this address too recently.
+    ///
     /// # Example
     ///
-    /// ```
-    /// use go_true::{Api, EmailOrPhone};
+    /// ```no_run
+    /// use go_true_redux::Client;
     ///
-    /// let url = "http://localhost:9998".to_string();
-    /// let mut client = Api::new(url);
-    /// let email = "random@mail.com".to_string();
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///     let email = "random@mail.com".to_string();
     ///
-    /// client.reset_password_for_email(&email);
+    ///     client.reset_password_for_email(&email).await?;
+    ///     Ok(())
+    /// }
     /// ```
-    pub async fn reset_password_for_email(&self, email: &str) -> Result<bool, reqwest::Error> {
+    pub async fn reset_password_for_email(&self, email: &str) -> Result<bool, Error> {
         let endpoint = format!("{}/recover", self.url);
 
+        let address = EmailOrPhone::Email(email.to_owned());
+        self.check_cooldown(&address)?;
+
         let body = json!({
             "email": &email,
         });
 
-        self.client
-            .post(endpoint)
-            .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?;
+        debug!("calling {}", endpoint);
+        let result = match self
+            .execute(
+                self.post(endpoint)
+                    .headers(self.headers.clone())
+                    .json(&body),
+            )
+            .await
+        {
+            Ok(resp) => handle_gotrue_resp(resp).await.map(|_| true),
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                Err(Error::InternalError)
+            }
+        };
 
-        Ok(true)
+        self.record_cooldown(&address, &result);
+        result
     }
 
     /// Returns the url for a given provider.
@@ -409,6 +363,21 @@ impl Client {
         format!("{}/authorize?provider={}", self.url, provider)
     }
 
+    /// Returns the url for a given provider, like [`Client::get_url_for_provider`],
+    /// after checking that the provider is enabled.
+    ///
+    /// The provider name is matched case-insensitively and sent in GoTrue's
+    /// lowercase spelling. With a [`SettingsCache`], fails with
+    /// [`Error::Disabled`] if the instance lists the provider as disabled.
+    /// Without one, nothing is checked.
+    pub async fn get_checked_url_for_provider(&self, provider: &str) -> Result<String, Error> {
+        let provider = Provider::from(provider);
+        let url = self.get_url_for_provider(provider.as_str());
+        self.check_provider(provider).await?;
+
+        Ok(url)
+    }
+
     /// Refreshes the current session by refresh token
     ///
     /// # Example
@@ -431,23 +400,16 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn refresh_access_token(
-        &self,
-        refresh_token: &str,
-    ) -> Result<Session, reqwest::Error> {
+    pub async fn refresh_access_token(&self, refresh_token: &str) -> Result<Session, Error> {
         let endpoint = format!("{}/token?grant_type=refresh_token", self.url);
         let body = json!({ "refresh_token": refresh_token });
 
-        let session: Session = self
-            .client
+        let request = self
             .post(endpoint)
             .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+            .json(&body);
+
+        let session: Session = gotrue_json(self.send_idempotent(request).await).await?;
 
         Ok(session)
     }
@@ -474,7 +436,7 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_user(&self, jwt: &str) -> Result<User, reqwest::Error> {
+    pub async fn get_user(&self, jwt: &str) -> Result<User, Error> {
         let endpoint = format!("{}/user", self.url);
 
         let mut headers: HeaderMap = self.headers.clone();
@@ -484,31 +446,29 @@ impl Client {
             HeaderValue::from_str(bearer.as_ref()).expect("Invalid header value."),
         );
 
-        let user: User = self
-            .client
-            .get(endpoint)
-            .headers(headers)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+        let request = self.get(endpoint).headers(headers);
+
+        let user: User = gotrue_json(self.send_idempotent(request).await).await?;
 
         Ok(user)
     }
 
     /// Updates a user
     ///
+    /// Only the attributes that are set are changed. Changing the email or phone
+    /// number sends a confirmation first. Fails with
+    /// [`Error::ReauthenticationRequired`] if GoTrue requires a nonce from
+    /// [`Client::reauthenticate`] to change the password.
+    ///
     /// # Example
     ///
-    /// ```
-    /// use go_true::{Api, EmailOrPhone};
-    /// use serde_json::json;
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailOrPhone, UserAttributes};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let url = "http://localhost:9998".to_string();
-    ///     let mut client = Api::new(url);
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
     ///
     ///     let email = "email@example.com".to_string();
     ///     let password = "Abcd1234!".to_string();
@@ -517,43 +477,41 @@ impl Client {
     ///         .await?;
     ///     let session = client.sign_in(EmailOrPhone::Email(email), &password).await?;
     ///
-    ///     let new_email = "otheremail@example.com";
-    ///     let attributes = UserAttributes {
-    ///         email: new_email.clone(),
-    ///         password: "Abcd12345!".to_string(),
-    ///         data: json!({ "test": "test" }),
-    ///     };
+    ///     let attributes = UserAttributes::default().with_password("Abcd12345!");
     ///
-    ///     let updatedUser = client.update_user(attributes, &session.access_token).await?;
+    ///     let updated_user = client.update_user(attributes, &session.access_token).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn update_user(
-        &self,
-        user: UserAttributes,
-        jwt: &str,
-    ) -> Result<UserUpdate, reqwest::Error> {
+    pub async fn update_user(&self, user: UserAttributes, jwt: &str) -> Result<User, Error> {
         let endpoint = format!("{}/user", self.url);
 
-        let mut headers: HeaderMap = self.headers.clone();
-        let bearer = format!("Bearer {jwt}");
-        headers.insert(
-            "Authorization",
-            HeaderValue::from_str(bearer.as_ref()).expect("Invalid header value."),
-        );
+        let mut request = self
+            .put(endpoint.as_str())
+            .headers(self.authorized_headers(jwt))
+            .json(&user);
+        if let Some(redirect_to) = &user.email_redirect_to {
+            request = request.query(&[("redirect_to", redirect_to)]);
+        }
 
-        let body = json!({"email": user.email, "password": user.password, "data": user.data});
+        debug!("calling {}", endpoint);
+        let resp = match self.execute(request).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
 
-        let user: UserUpdate = self
-            .client
-            .put(endpoint)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<UserUpdate>()
-            .await?;
+        let resp = handle_gotrue_resp(resp).await?;
+
+        let user = match resp.json::<User>().await {
+            Ok(user) => user,
+            Err(e) => {
+                error!("could not deserialize the response into a user: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
 
         Ok(user)
     }
@@ -577,73 +535,22 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn invite_user_by_email(&self, email: &str) -> Result<User, reqwest::Error> {
+    pub async fn invite_user_by_email(&self, email: &str) -> Result<User, Error> {
         let endpoint = format!("{}/invite", self.url);
 
         let body = json!({
             "email": &email,
         });
 
-        let user: User = self
-            .client
+        let request = self
             .post(endpoint)
             .headers(self.headers.clone())
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<User>()
-            .await?;
+            .json(&body);
+        let user: User = gotrue_json(self.execute(request).await).await?;
 
         Ok(user)
     }
 
-    /// Lists all users based on a query string
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use go_true::{Api, EmailOrPhone};
-    ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let url = "http://localhost:9998".to_string();
-    ///     let mut client = Api::new(url);
-    ///
-    ///     let email = "email@example.com".to_string();
-    ///     let password = "Abcd1234!".to_string();
-    ///
-    ///     client
-    ///         .sign_up(EmailOrPhone::Email(email), &password)
-    ///         .await?;
-    ///
-    ///     let users = client.list_users(None).await?;
-    ///
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn list_users(
-        &self,
-        query_string: Option<String>,
-    ) -> Result<UserList, reqwest::Error> {
-        let endpoint = match query_string {
-            Some(query) => format!("{}/admin/users{}", self.url, query),
-            None => format!("{}/admin/users", self.url),
-        };
-
-        let users: UserList = self
-            .client
-            .get(endpoint)
-            .headers(self.headers.clone())
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
-
-        Ok(users)
-    }
-
     /// Gets a user by id
     ///
     /// # Example
@@ -668,18 +575,12 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_user_by_id(&self, user_id: &str) -> Result<User, reqwest::Error> {
+    pub async fn get_user_by_id(&self, user_id: &str) -> Result<User, Error> {
         let endpoint = format!("{}/admin/users/{}", self.url, user_id);
 
-        let user: User = self
-            .client
-            .get(endpoint)
-            .headers(self.headers.clone())
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+        let request = self.get(endpoint).headers(self.headers.clone());
+
+        let user: User = gotrue_json(self.send_idempotent(request).await).await?;
 
         Ok(user)
     }
@@ -724,21 +625,13 @@ impl Client {
         &self,
         id: &str,
         user: T,
-    ) -> Result<User, reqwest::Error> {
+    ) -> Result<User, Error> {
         let endpoint = format!("{}/admin/users/{}", self.url, id);
 
         let json = serde_json::to_value(&user).unwrap();
 
-        let client = reqwest::Client::new();
-        let user: User = client
-            .put(endpoint)
-            .headers(self.headers.clone())
-            .json(&json)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+        let request = self.put(endpoint).headers(self.headers.clone()).json(&json);
+        let user: User = gotrue_json(self.execute(request).await).await?;
 
         Ok(user)
     }
@@ -762,30 +655,98 @@ impl Client {
     ///         email_confirmed: None,
     ///         phone_confirmed: None,
     ///     };
-
+    ///
     ///     let user = client.create_user(user).await?;
     ///     client.delete_user(&user.id).await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_user(&self, user_id: &str) -> Result<bool, reqwest::Error> {
+    pub async fn delete_user(&self, user_id: &str) -> Result<bool, Error> {
         let endpoint = format!("{}/admin/users/{}", self.url, user_id);
 
-        self.client
-            .delete(endpoint)
-            .headers(self.headers.clone())
-            .send()
-            .await?
-            .error_for_status()?;
+        let request = self.delete(endpoint).headers(self.headers.clone());
+        gotrue_resp(self.execute(request).await).await?;
 
         Ok(true)
     }
 }
 
-fn handle_gotrue_resp_status(status_code: StatusCode) -> Result<(), Error> {
-    if status_code.is_success() {
-        return Ok(());
+/// The error body returned by GoTrue. Most endpoints answer with `msg`, the
+/// token endpoint with the OAuth2 style `error` and `error_description`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ErrorBody {
+    error_code: Option<String>,
+    msg: Option<String>,
+    message: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+/// Maps an unsuccessful GoTrue response to an [`Error`].
+async fn handle_gotrue_resp(resp: Response) -> Result<Response, Error> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    error!("gotrue returned an error status: {}", status);
+    let retry_after = retry::retry_after(&resp);
+    let body = resp.json::<ErrorBody>().await.unwrap_or_default();
+    let error_code = body.error_code.unwrap_or_default();
+    let message = body
+        .msg
+        .or(body.message)
+        .or(body.error_description)
+        .or(body.error)
+        .unwrap_or_default();
+
+    Err(match status {
+        StatusCode::TOO_MANY_REQUESTS => Error::RateLimited {
+            retry_after: retry_after.or_else(|| rate_limit::parse_retry_after(&message)),
+        },
+        StatusCode::UNAUTHORIZED => Error::NotAuthenticated,
+        _ if error_code == "reauthentication_needed"
+            || message.to_lowercase().contains("requires reauthentication") =>
+        {
+            Error::ReauthenticationRequired
+        }
+        _ if message.contains("already registered")
+            || message.contains("already been registered") =>
+        {
+            Error::AlreadySignedUp
+        }
+        status if status.is_server_error() => Error::InternalError,
+        status => Error::Api {
+            status: status.as_u16(),
+            message,
+        },
+    })
+}
+
+/// Maps a request that got no response, or an unsuccessful GoTrue response, to an [`Error`].
+pub(crate) async fn gotrue_resp(sent: Result<Response, TransportError>) -> Result<Response, Error> {
+    match sent {
+        Ok(resp) => handle_gotrue_resp(resp).await,
+        Err(e) => {
+            error!("could not make request to gotrue: {}", e);
+            Err(Error::InternalError)
+        }
+    }
+}
+
+/// Like [`gotrue_resp`], deserializing the successful response.
+pub(crate) async fn gotrue_json<T: DeserializeOwned>(
+    sent: Result<Response, TransportError>,
+) -> Result<T, Error> {
+    let resp = gotrue_resp(sent).await?;
+
+    match resp.json::<T>().await {
+        Ok(body) => Ok(body),
+        Err(e) => {
+            error!("could not deserialize the response: {}", e);
+            Err(Error::InternalError)
+        }
     }
-    Ok(())
 }
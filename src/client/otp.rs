@@ -0,0 +1,136 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{debug, error};
+
+use crate::{Client, EmailOrPhone, Error};
+
+use super::handle_gotrue_resp;
+
+/// The channel an SMS OTP is delivered through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpChannel {
+    /// A text message.
+    #[default]
+    Sms,
+    /// A WhatsApp message.
+    Whatsapp,
+}
+
+/// Options for [`Client::send_otp`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtpOptions {
+    /// Whether to sign the user up if they do not exist yet. Defaults to `true`.
+    pub create_user: bool,
+    /// Metadata for the user, if they are signed up by this call.
+    pub data: Option<Value>,
+    /// Where to send the user after they click the link of an email OTP.
+    /// Defaults to the site URL.
+    pub email_redirect_to: Option<String>,
+    /// The channel used for phone OTPs. Ignored for emails.
+    pub channel: OtpChannel,
+    /// The captcha token, if captcha protection is enabled.
+    pub captcha_token: Option<String>,
+}
+
+impl Default for OtpOptions {
+    fn default() -> Self {
+        OtpOptions {
+            create_user: true,
+            data: None,
+            email_redirect_to: None,
+            channel: OtpChannel::default(),
+            captcha_token: None,
+        }
+    }
+}
+
+impl OtpOptions {
+    fn to_body(&self, email_or_phone: &EmailOrPhone) -> Value {
+        let mut body = match email_or_phone {
+            EmailOrPhone::Email(email) => json!({ "email": email }),
+            EmailOrPhone::Phone(phone) => json!({
+                "phone": phone,
+                "channel": self.channel,
+            }),
+        };
+
+        body["create_user"] = json!(self.create_user);
+        if let Some(data) = &self.data {
+            body["data"] = data.clone();
+        }
+        if let Some(captcha_token) = &self.captcha_token {
+            body["gotrue_meta_security"] = json!({ "captcha_token": captcha_token });
+        }
+
+        body
+    }
+}
+
+impl Client {
+    /// Sends an OTP Code and creates user if it does not exist
+    ///
+    /// Emails receive a magic link, phones a code by SMS or WhatsApp.
+    /// Fails with [`Error::RateLimited`] if an OTP was sent to this address too recently,
+    /// and with [`Error::Disabled`] if a [`crate::SettingsCache`] shows the channel is off.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailOrPhone, Error, OtpChannel, OtpOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let phone = "+15555550100".to_string();
+    ///     let options = OtpOptions {
+    ///         create_user: false,
+    ///         channel: OtpChannel::Whatsapp,
+    ///         ..OtpOptions::default()
+    ///     };
+    ///
+    ///     match client.send_otp(EmailOrPhone::Phone(phone), Some(options)).await {
+    ///         Err(Error::RateLimited { retry_after }) => println!("try again in {:?}", retry_after),
+    ///         result => {
+    ///             result?;
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn send_otp(
+        &self,
+        email_or_phone: EmailOrPhone,
+        options: Option<OtpOptions>,
+    ) -> Result<bool, Error> {
+        let endpoint = format!("{}/otp", self.url);
+        let options = options.unwrap_or_default();
+
+        self.check_cooldown(&email_or_phone)?;
+        self.check_sign_in(&email_or_phone).await?;
+
+        let mut request = self
+            .post(endpoint.as_str())
+            .headers(self.headers.clone())
+            .json(&options.to_body(&email_or_phone));
+        if let (EmailOrPhone::Email(_), Some(redirect_to)) =
+            (&email_or_phone, &options.email_redirect_to)
+        {
+            request = request.query(&[("redirect_to", redirect_to)]);
+        }
+
+        debug!("calling {}", endpoint);
+        let result = match self.execute(request).await {
+            Ok(resp) => handle_gotrue_resp(resp).await.map(|_| true),
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                Err(Error::InternalError)
+            }
+        };
+
+        self.record_cooldown(&email_or_phone, &result);
+        result
+    }
+}
@@ -0,0 +1,96 @@
+use std::future::Future;
+
+use futures_util::{stream, Stream, TryStreamExt};
+use http::header::LINK;
+
+use crate::{client::request::Response, Error};
+
+/// A page of items, with the pagination GoTrue reported alongside it.
+pub(crate) struct Page<T> {
+    pub(crate) items: Vec<T>,
+    pub(crate) next_page: Option<u32>,
+    pub(crate) total: Option<u64>,
+}
+
+/// Reads the page number of the `rel="next"` entry of a `Link` header.
+///
+/// GoTrue sends relative links, which are resolved against the request URL.
+pub(crate) fn next_page(resp: &Response) -> Option<u32> {
+    let link = resp.headers().get(LINK)?.to_str().ok()?;
+
+    link.split(',')
+        .find(|entry| entry.contains("rel=\"next\""))
+        .and_then(|entry| {
+            let start = entry.find('<')? + 1;
+            let end = entry.find('>')?;
+            resp.url().join(entry.get(start..end)?).ok()
+        })
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "page")
+                .and_then(|(_, page)| page.parse().ok())
+        })
+}
+
+/// Reads the total number of items of a paginated response.
+pub(crate) fn total_count(resp: &Response) -> Option<u64> {
+    resp.headers()
+        .get("x-total-count")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+/// Works out the page following `page`, from the pagination GoTrue reported
+/// or, failing that, from whether the page was full. An empty page is always
+/// the last one.
+pub(crate) fn following_page(
+    page: u32,
+    per_page: u32,
+    next_page: Option<u32>,
+    total: Option<u64>,
+    len: usize,
+) -> Option<u32> {
+    if len == 0 {
+        return None;
+    }
+
+    match (next_page, total) {
+        (Some(next), _) => (next > page).then_some(next),
+        (None, Some(total)) => (u64::from(page) * u64::from(per_page) < total).then_some(page + 1),
+        (None, None) => (per_page > 0 && len as u32 >= per_page).then_some(page + 1),
+    }
+}
+
+/// Streams the items of every page from `first` on, fetching each page with
+/// `fetch` as the previous one is consumed.
+pub(crate) fn walk_pages<'a, T, F, Fut>(
+    first: u32,
+    per_page: u32,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    T: 'a,
+    F: Fn(u32) -> Fut + 'a,
+    Fut: Future<Output = Result<Page<T>, Error>> + 'a,
+{
+    stream::try_unfold(Some(first), move |page| {
+        let request = page.map(|page| (page, fetch(page)));
+        async move {
+            let (page, request) = match request {
+                Some(request) => request,
+                None => return Ok::<_, Error>(None),
+            };
+
+            let Page {
+                items,
+                next_page,
+                total,
+            } = request.await?;
+            let next = following_page(page, per_page, next_page, total, items.len());
+
+            Ok(Some((items, next)))
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
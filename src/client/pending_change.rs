@@ -0,0 +1,152 @@
+use serde_json::Value;
+use tracing::{debug, error};
+
+use crate::{
+    client::verify::VerifyParams, session::Session, user::User, Client, EmailOrPhone, Error,
+    UserAttributes,
+};
+
+/// The result of confirming an email change with [`Client::verify_email_change`].
+#[derive(Debug, Clone)]
+pub enum EmailChangeConfirmation {
+    /// The token was accepted, but GoTrue requires secure email change and the
+    /// token sent to the other address must be verified too.
+    AwaitingOtherAddress,
+    /// The email was changed, and the user signed in with it.
+    Confirmed(Box<Session>),
+}
+
+impl Client {
+    /// Sends the confirmation of the user's pending email change again.
+    ///
+    /// Fails with [`Error::NoPendingChange`] if the user is not changing their
+    /// email, and with [`Error::RateLimited`] if a confirmation was sent too recently.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, UserAttributes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///     let jwt = "user.access.token";
+    ///
+    ///     let attributes = UserAttributes::default().with_email("new@example.com");
+    ///     client.update_user(attributes, jwt).await?;
+    ///
+    ///     // The user did not receive the email.
+    ///     let user = client.resend_email_change(jwt).await?;
+    ///     println!("sent again at {:?}", user.email_change_sent_at);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn resend_email_change(&self, jwt: &str) -> Result<User, Error> {
+        let user = self.get_user(jwt).await?;
+        let new_email = user.new_email.ok_or(Error::NoPendingChange)?;
+
+        let address = EmailOrPhone::Email(new_email.clone());
+        self.check_cooldown(&address)?;
+
+        let result = self
+            .update_user(UserAttributes::default().with_email(new_email), jwt)
+            .await;
+
+        self.record_cooldown(&address, &result);
+        result
+    }
+
+    /// Sends the OTP of the user's pending phone change again.
+    ///
+    /// Fails with [`Error::NoPendingChange`] if the user is not changing their
+    /// phone number, and with [`Error::RateLimited`] if an OTP was sent too recently.
+    pub async fn resend_phone_change(&self, jwt: &str) -> Result<User, Error> {
+        let user = self.get_user(jwt).await?;
+        let new_phone = user.new_phone.ok_or(Error::NoPendingChange)?;
+
+        let address = EmailOrPhone::Phone(new_phone.clone());
+        self.check_cooldown(&address)?;
+
+        let result = self
+            .update_user(UserAttributes::default().with_phone(new_phone), jwt)
+            .await;
+
+        self.record_cooldown(&address, &result);
+        result
+    }
+
+    /// Confirms an email change with the OTP sent to `email`.
+    ///
+    /// When secure email change is enabled, GoTrue sends an OTP to both the
+    /// current and the new address, and the change only completes once both are
+    /// verified. Fails with [`Error::WrongToken`] if the OTP is invalid or has expired.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailChangeConfirmation};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     match client.verify_email_change("old@example.com", "123456").await? {
+    ///         EmailChangeConfirmation::AwaitingOtherAddress => {
+    ///             println!("now confirm the code sent to the new address");
+    ///         }
+    ///         EmailChangeConfirmation::Confirmed(session) => {
+    ///             println!("email changed to {}", session.user.email);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify_email_change(
+        &self,
+        email: &str,
+        token: &str,
+    ) -> Result<EmailChangeConfirmation, Error> {
+        let params = VerifyParams::EmailChange {
+            email: email.to_owned(),
+            token: token.to_owned(),
+        };
+        let resp = self.send_verify(&params).await?;
+
+        let body = match resp.json::<Value>().await {
+            Ok(body) => body,
+            Err(e) => {
+                error!("could not deserialize the response: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        if body.get("access_token").is_none() {
+            debug!("email change accepted, awaiting the other address");
+            return Ok(EmailChangeConfirmation::AwaitingOtherAddress);
+        }
+
+        match serde_json::from_value::<Session>(body) {
+            Ok(session) => Ok(EmailChangeConfirmation::Confirmed(Box::new(session))),
+            Err(e) => {
+                error!("could not deserialize the response into a session: {}", e);
+                Err(Error::InternalError)
+            }
+        }
+    }
+
+    /// Confirms a phone change with the OTP sent by SMS to the new number.
+    ///
+    /// Fails with [`Error::WrongToken`] if the OTP is invalid or has expired.
+    pub async fn verify_phone_change(&self, phone: &str, token: &str) -> Result<Session, Error> {
+        let params = VerifyParams::PhoneChange {
+            phone: phone.to_owned(),
+            token: token.to_owned(),
+        };
+
+        Ok(self.verify_otp(params).await?.session)
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, warn};
+
+use crate::{client::settings::Provider, Client, EmailOrPhone, Error, Settings};
+
+/// Something a GoTrue instance can have disabled, reported by [`Error::Disabled`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Signing up new users.
+    Signup,
+    /// Signing in with an email.
+    Email,
+    /// Signing in with a phone number.
+    Phone,
+    /// Signing in with an OAuth provider.
+    Provider(Provider),
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Signup => f.write_str("signup"),
+            Capability::Email => f.write_str("email sign in"),
+            Capability::Phone => f.write_str("phone sign in"),
+            Capability::Provider(provider) => write!(f, "the {provider} provider"),
+        }
+    }
+}
+
+/// Caches the [`Settings`] of a GoTrue instance to check calls against them.
+///
+/// Once a cache is set with [`Client::with_settings_cache`], [`Client::sign_up`],
+/// [`Client::send_otp`] and [`Client::get_checked_url_for_provider`] fail fast with
+/// [`Error::Disabled`] when the instance has the channel or provider turned
+/// off, instead of making a call that is bound to fail. The settings are
+/// fetched on first use and again once they are older than the time to live.
+/// Clones share the same settings.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use go_true_redux::{Client, SettingsCache};
+///
+/// let cache = SettingsCache::new(Duration::from_secs(60));
+/// let client = Client::new("http://your.gotrue.endpoint").with_settings_cache(cache.clone());
+///
+/// assert_eq!(cache.get(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SettingsCache {
+    ttl: Duration,
+    cached: Arc<Mutex<Option<(Instant, Settings)>>>,
+}
+
+impl Default for SettingsCache {
+    /// A cache keeping the settings for five minutes.
+    fn default() -> Self {
+        SettingsCache::new(Duration::from_secs(300))
+    }
+}
+
+impl SettingsCache {
+    /// Creates a cache keeping the settings for the given time to live.
+    pub fn new(ttl: Duration) -> Self {
+        SettingsCache {
+            ttl,
+            cached: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The cached settings, if they were fetched and are still fresh.
+    pub fn get(&self) -> Option<Settings> {
+        let mut cached = self.cached.lock().expect("settings cache poisoned");
+
+        match &*cached {
+            Some((fetched_at, settings)) if fetched_at.elapsed() < self.ttl => {
+                Some(settings.clone())
+            }
+            Some(_) => {
+                *cached = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Forgets the cached settings, so they are fetched again on next use.
+    pub fn clear(&self) {
+        let mut cached = self.cached.lock().expect("settings cache poisoned");
+        *cached = None;
+    }
+
+    pub(crate) fn store(&self, settings: &Settings) {
+        let mut cached = self.cached.lock().expect("settings cache poisoned");
+        *cached = Some((Instant::now(), settings.clone()));
+    }
+}
+
+impl Client {
+    /// Sets a [`SettingsCache`] to check sign-ups, OTPs and providers against
+    /// the settings of the instance before calling it.
+    pub fn with_settings_cache(mut self, cache: SettingsCache) -> Self {
+        self.settings_cache = Some(cache);
+        self
+    }
+
+    /// The settings to check a call against, if a cache is set.
+    ///
+    /// Settings that cannot be fetched skip the check, leaving GoTrue to
+    /// answer the call itself.
+    async fn preflight_settings(&self) -> Option<Settings> {
+        let cache = self.settings_cache.as_ref()?;
+        if let Some(settings) = cache.get() {
+            return Some(settings);
+        }
+
+        match self.get_settings().await {
+            Ok(settings) => Some(settings),
+            Err(e) => {
+                warn!("could not fetch settings to check the call against: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Fails with [`Error::Disabled`] if signing up with the address is disabled.
+    pub(crate) async fn check_sign_up(&self, address: &EmailOrPhone) -> Result<(), Error> {
+        let settings = match self.preflight_settings().await {
+            Some(settings) => settings,
+            None => return Ok(()),
+        };
+
+        if settings.disable_signup {
+            return disabled(Capability::Signup);
+        }
+        check_channel(&settings, address)
+    }
+
+    /// Fails with [`Error::Disabled`] if signing in with the address is disabled.
+    pub(crate) async fn check_sign_in(&self, address: &EmailOrPhone) -> Result<(), Error> {
+        match self.preflight_settings().await {
+            Some(settings) => check_channel(&settings, address),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails with [`Error::Disabled`] if the OAuth provider is disabled. Providers
+    /// the instance does not list are let through for GoTrue to judge.
+    pub(crate) async fn check_provider(&self, provider: Provider) -> Result<(), Error> {
+        match self.preflight_settings().await {
+            Some(settings) if settings.supports_provider(provider.clone()) == Some(false) => {
+                disabled(Capability::Provider(provider))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_channel(settings: &Settings, address: &EmailOrPhone) -> Result<(), Error> {
+    match address {
+        EmailOrPhone::Email(_) if settings.external.email == Some(false) => {
+            disabled(Capability::Email)
+        }
+        EmailOrPhone::Phone(_) if settings.external.phone == Some(false) => {
+            disabled(Capability::Phone)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn disabled(capability: Capability) -> Result<(), Error> {
+    debug!("{} is disabled, not calling gotrue", capability);
+    Err(Error::Disabled { capability })
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+use crate::{Client, EmailOrPhone, Error};
+
+/// Keeps track of when an email address or phone number may be sent another
+/// OTP, magic link or recovery message.
+///
+/// GoTrue only allows one such message per address every 60 seconds by default.
+/// Once a tracker is set with [`Client::with_rate_limit_tracker`], the client
+/// records every successful send and every [`Error::RateLimited`] response, and
+/// fails fast with [`Error::RateLimited`] while an address is still cooling
+/// down. Clones share the same state, so a UI can keep one to show countdowns.
+///
+/// # Example
+///
+/// ```
+/// use go_true_redux::{Client, EmailOrPhone, RateLimitTracker};
+///
+/// let tracker = RateLimitTracker::default();
+/// let client = Client::new("http://your.gotrue.endpoint").with_rate_limit_tracker(tracker.clone());
+///
+/// let email = EmailOrPhone::Email("email@example.com".to_string());
+/// assert_eq!(tracker.remaining(&email), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimitTracker {
+    cooldown: Duration,
+    until: Arc<Mutex<HashMap<EmailOrPhone, Instant>>>,
+}
+
+impl Default for RateLimitTracker {
+    /// A tracker using GoTrue's default cooldown of 60 seconds.
+    fn default() -> Self {
+        RateLimitTracker::new(Duration::from_secs(60))
+    }
+}
+
+impl RateLimitTracker {
+    /// Creates a tracker assuming the given cooldown after every successful send.
+    ///
+    /// This should match the rate limit configured on the GoTrue instance.
+    pub fn new(cooldown: Duration) -> Self {
+        RateLimitTracker {
+            cooldown,
+            until: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// How long until another message may be sent to this address, if it is
+    /// still cooling down.
+    pub fn remaining(&self, address: &EmailOrPhone) -> Option<Duration> {
+        let mut until = self.until.lock().expect("rate limit tracker poisoned");
+        let now = Instant::now();
+
+        match until.get(address) {
+            Some(deadline) if *deadline > now => Some(*deadline - now),
+            Some(_) => {
+                until.remove(address);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Forgets the cooldown of an address.
+    pub fn clear(&self, address: &EmailOrPhone) {
+        let mut until = self.until.lock().expect("rate limit tracker poisoned");
+        until.remove(address);
+    }
+
+    fn start(&self, address: &EmailOrPhone, cooldown: Duration) {
+        let mut until = self.until.lock().expect("rate limit tracker poisoned");
+        until.insert(address.clone(), Instant::now() + cooldown);
+    }
+}
+
+/// Extracts the wait from GoTrue's rate limit messages, e.g. "For security
+/// purposes, you can only request this after 42 seconds."
+pub(crate) fn parse_retry_after(message: &str) -> Option<Duration> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+    let position = words.iter().position(|word| word.starts_with("second"))?;
+    let seconds = words.get(position.checked_sub(1)?)?.parse::<u64>().ok()?;
+
+    Some(Duration::from_secs(seconds))
+}
+
+impl Client {
+    /// Sets a [`RateLimitTracker`] to track OTP, magic link and recovery cooldowns.
+    pub fn with_rate_limit_tracker(mut self, tracker: RateLimitTracker) -> Self {
+        self.rate_limit_tracker = Some(tracker);
+        self
+    }
+
+    /// Fails with [`Error::RateLimited`] if the address is still cooling down.
+    pub(crate) fn check_cooldown(&self, address: &EmailOrPhone) -> Result<(), Error> {
+        let tracker = match &self.rate_limit_tracker {
+            Some(tracker) => tracker,
+            None => return Ok(()),
+        };
+
+        match tracker.remaining(address) {
+            Some(remaining) => {
+                debug!("{:?} is cooling down for {:?}", address, remaining);
+                Err(Error::RateLimited {
+                    retry_after: Some(remaining),
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Starts the cooldown of an address after a send attempt.
+    pub(crate) fn record_cooldown<T>(&self, address: &EmailOrPhone, result: &Result<T, Error>) {
+        let tracker = match &self.rate_limit_tracker {
+            Some(tracker) => tracker,
+            None => return,
+        };
+
+        match result {
+            Ok(_) => tracker.start(address, tracker.cooldown),
+            Err(Error::RateLimited {
+                retry_after: Some(retry_after),
+            }) => tracker.start(address, *retry_after),
+            Err(_) => {}
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use tracing::{debug, error};
+
+use crate::{Client, Error};
+
+use super::handle_gotrue_resp;
+
+impl Client {
+    /// Sends a nonce to the user's email (preferred) or phone.
+    ///
+    /// This endpoint requires the user to be logged in / authenticated first.
+    /// The user needs to have either an email or phone number for the nonce to be sent successfully.
+    /// The nonce is then passed in [`crate::UserAttributes::nonce`] to change the password.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailOrPhone, UserAttributes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let email = "email@example.com".to_string();
+    ///     let session = client.sign_in(EmailOrPhone::Email(email), "Abcd1234!").await?;
+    ///
+    ///     client.reauthenticate(&session.access_token).await?;
+    ///
+    ///     let attributes = UserAttributes::default()
+    ///         .with_password("Abcd12345!")
+    ///         .with_nonce("123456");
+    ///     client.update_user(attributes, &session.access_token).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn reauthenticate(&self, jwt: &str) -> Result<bool, Error> {
+        let endpoint = format!("{}/reauthenticate", self.url);
+
+        debug!("calling {}", endpoint);
+        let resp = match self
+            .execute(self.get(endpoint).headers(self.authorized_headers(jwt)))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        handle_gotrue_resp(resp).await?;
+
+        Ok(true)
+    }
+}
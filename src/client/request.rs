@@ -0,0 +1,162 @@
+use http::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::{
+    client::transport::{HttpRequest, HttpResponse, TransportError, TransportErrorKind},
+    Client,
+};
+
+#[derive(Debug, Clone)]
+struct Parts {
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+/// Builds a request to GoTrue, keeping the first error until it is sent.
+#[derive(Debug)]
+pub(crate) struct RequestBuilder {
+    parts: Result<Parts, TransportError>,
+}
+
+impl RequestBuilder {
+    fn new(method: Method, url: &str) -> Self {
+        let parts = Url::parse(url)
+            .map(|url| Parts {
+                method,
+                url,
+                headers: HeaderMap::new(),
+                body: Vec::new(),
+            })
+            .map_err(|e| TransportError::new(TransportErrorKind::Request, e));
+
+        RequestBuilder { parts }
+    }
+
+    fn and_then(mut self, f: impl FnOnce(&mut Parts) -> Result<(), TransportError>) -> Self {
+        if let Ok(parts) = &mut self.parts {
+            if let Err(e) = f(parts) {
+                self.parts = Err(e);
+            }
+        }
+        self
+    }
+
+    /// Adds the headers, replacing any already set with the same names.
+    pub(crate) fn headers(self, headers: HeaderMap) -> Self {
+        self.and_then(|parts| {
+            parts.headers.extend(headers);
+            Ok(())
+        })
+    }
+
+    /// Sets the body to the value serialized as JSON.
+    pub(crate) fn json<T: Serialize + ?Sized>(self, body: &T) -> Self {
+        self.and_then(|parts| {
+            parts.body = serde_json::to_vec(body)
+                .map_err(|e| TransportError::new(TransportErrorKind::Request, e))?;
+            if !parts.headers.contains_key(CONTENT_TYPE) {
+                parts
+                    .headers
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            }
+            Ok(())
+        })
+    }
+
+    /// Appends the value, serialized as pairs, to the query string.
+    pub(crate) fn query<T: Serialize + ?Sized>(self, query: &T) -> Self {
+        self.and_then(|parts| {
+            let pairs = serde_urlencoded::to_string(query)
+                .map_err(|e| TransportError::new(TransportErrorKind::Request, e))?;
+            if pairs.is_empty() {
+                return Ok(());
+            }
+
+            let query = match parts.url.query() {
+                Some(existing) if !existing.is_empty() => format!("{existing}&{pairs}"),
+                _ => pairs,
+            };
+            parts.url.set_query(Some(&query));
+            Ok(())
+        })
+    }
+
+    /// A copy of the request, to send it again. `None` if building it failed.
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        let parts = self.parts.as_ref().ok()?.clone();
+        Some(RequestBuilder { parts: Ok(parts) })
+    }
+
+    /// The request for the transport, and its URL.
+    pub(crate) fn build(self) -> Result<(HttpRequest, Url), TransportError> {
+        let parts = self.parts?;
+
+        let mut builder = http::Request::builder()
+            .method(parts.method)
+            .uri(parts.url.as_str());
+        if let Some(headers) = builder.headers_mut() {
+            *headers = parts.headers;
+        }
+
+        let request = builder
+            .body(parts.body)
+            .map_err(|e| TransportError::new(TransportErrorKind::Request, e))?;
+
+        Ok((request, parts.url))
+    }
+}
+
+/// A response from GoTrue, with the URL of the request that got it.
+#[derive(Debug)]
+pub(crate) struct Response {
+    url: Url,
+    inner: HttpResponse,
+}
+
+impl Response {
+    pub(crate) fn new(url: Url, inner: HttpResponse) -> Self {
+        Response { url, inner }
+    }
+
+    pub(crate) fn status(&self) -> StatusCode {
+        self.inner.status()
+    }
+
+    pub(crate) fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+
+    /// The URL the request was sent to.
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Deserializes the body as JSON.
+    pub(crate) async fn json<T: DeserializeOwned>(self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(self.inner.body())
+    }
+}
+
+impl Client {
+    pub(crate) fn get(&self, url: impl AsRef<str>) -> RequestBuilder {
+        RequestBuilder::new(Method::GET, url.as_ref())
+    }
+
+    pub(crate) fn post(&self, url: impl AsRef<str>) -> RequestBuilder {
+        RequestBuilder::new(Method::POST, url.as_ref())
+    }
+
+    pub(crate) fn put(&self, url: impl AsRef<str>) -> RequestBuilder {
+        RequestBuilder::new(Method::PUT, url.as_ref())
+    }
+
+    pub(crate) fn delete(&self, url: impl AsRef<str>) -> RequestBuilder {
+        RequestBuilder::new(Method::DELETE, url.as_ref())
+    }
+}
@@ -0,0 +1,141 @@
+use serde_json::{json, Value};
+use tracing::{debug, error};
+
+use crate::{client::verify::VerificationType, Client, EmailOrPhone, Error};
+
+use super::handle_gotrue_resp;
+
+/// A confirmation to send again with [`Client::resend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResendParams {
+    /// The confirmation email of a new user.
+    Signup {
+        /// The email of the user.
+        email: String,
+    },
+    /// The confirmation of a pending email change, sent to the new address.
+    EmailChange {
+        /// The current email of the user, not the one being changed to.
+        email: String,
+    },
+    /// The SMS OTP confirming the phone number of a new user.
+    Sms {
+        /// The phone number of the user.
+        phone: String,
+    },
+    /// The SMS OTP of a pending phone change, sent to the new number.
+    PhoneChange {
+        /// The current phone number of the user, not the one being changed to.
+        phone: String,
+    },
+}
+
+impl ResendParams {
+    /// The kind of token being sent again, to verify with [`Client::verify_otp`].
+    pub fn verification_type(&self) -> VerificationType {
+        match self {
+            ResendParams::Signup { .. } => VerificationType::Signup,
+            ResendParams::EmailChange { .. } => VerificationType::EmailChange,
+            ResendParams::Sms { .. } => VerificationType::Sms,
+            ResendParams::PhoneChange { .. } => VerificationType::PhoneChange,
+        }
+    }
+
+    fn email_or_phone(&self) -> EmailOrPhone {
+        match self {
+            ResendParams::Signup { email } | ResendParams::EmailChange { email } => {
+                EmailOrPhone::Email(email.clone())
+            }
+            ResendParams::Sms { phone } | ResendParams::PhoneChange { phone } => {
+                EmailOrPhone::Phone(phone.clone())
+            }
+        }
+    }
+}
+
+/// Options for [`Client::resend`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResendOptions {
+    /// Where to send the user after they click the link of an email.
+    /// Defaults to the site URL. Ignored for phones.
+    pub email_redirect_to: Option<String>,
+    /// The captcha token, if captcha protection is enabled.
+    pub captcha_token: Option<String>,
+}
+
+impl ResendOptions {
+    fn to_body(&self, params: &ResendParams) -> Value {
+        let mut body = match params.email_or_phone() {
+            EmailOrPhone::Email(email) => json!({ "email": email }),
+            EmailOrPhone::Phone(phone) => json!({ "phone": phone }),
+        };
+
+        body["type"] = json!(params.verification_type());
+        if let Some(captcha_token) = &self.captcha_token {
+            body["gotrue_meta_security"] = json!({ "captcha_token": captcha_token });
+        }
+
+        body
+    }
+}
+
+impl Client {
+    /// Sends a signup or change confirmation again, for users who lost the first one.
+    ///
+    /// Fails with [`Error::RateLimited`] if a confirmation was sent to this address too recently.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, ResendOptions, ResendParams};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let params = ResendParams::Signup {
+    ///         email: "email@example.com".to_string(),
+    ///     };
+    ///     let options = ResendOptions {
+    ///         email_redirect_to: Some("https://example.com/welcome".to_string()),
+    ///         ..ResendOptions::default()
+    ///     };
+    ///     client.resend(params, Some(options)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn resend(
+        &self,
+        params: ResendParams,
+        options: Option<ResendOptions>,
+    ) -> Result<bool, Error> {
+        let endpoint = format!("{}/resend", self.url);
+        let options = options.unwrap_or_default();
+
+        let address = params.email_or_phone();
+        self.check_cooldown(&address)?;
+
+        let mut request = self
+            .post(endpoint.as_str())
+            .headers(self.headers.clone())
+            .json(&options.to_body(&params));
+        if let (EmailOrPhone::Email(_), Some(redirect_to)) = (&address, &options.email_redirect_to)
+        {
+            request = request.query(&[("redirect_to", redirect_to)]);
+        }
+
+        debug!("calling {}", endpoint);
+        let result = match self.execute(request).await {
+            Ok(resp) => handle_gotrue_resp(resp).await.map(|_| true),
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                Err(Error::InternalError)
+            }
+        };
+
+        self.record_cooldown(&address, &result);
+        result
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime},
+};
+
+use http::{header::RETRY_AFTER, StatusCode};
+use tracing::{debug, warn};
+
+use crate::{
+    client::{
+        request::{RequestBuilder, Response},
+        transport::{TransportError, TransportErrorKind},
+    },
+    Client,
+};
+
+/// Describes how a [`Client`] retries requests that failed for transient reasons.
+///
+/// Only calls that are safe to replay are ever retried: reads such as
+/// [`Client::get_user_by_id`] or [`Client::list_users`], and refresh token
+/// grants. A request is retried when the connection fails or times out, or when
+/// GoTrue (or a gateway in front of it) answers with `429`, `502`, `503` or `504`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use go_true_redux::{Client, RetryPolicy};
+///
+/// let policy = RetryPolicy::default()
+///     .with_max_attempts(5)
+///     .with_initial_backoff(Duration::from_millis(200));
+///
+/// let client = Client::new("http://your.gotrue.endpoint").with_retry_policy(policy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting at a 100ms backoff capped at 5s, with jitter.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sets the total number of attempts, including the first one.
+    ///
+    /// A value of `1` disables retries.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the backoff applied before the first retry. It doubles on every
+    /// following retry.
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound for a single backoff.
+    ///
+    /// A `Retry-After` header asking for a longer wait than this gives up
+    /// retrying and returns the response as is.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Whether to randomize each backoff between zero and its computed value.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The total number of attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The backoff before retry number `retry` (starting at 1), without jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_backoff).then_some(retry_after);
+        }
+
+        let backoff = self.backoff(retry);
+        if !self.jitter || backoff.is_zero() {
+            return Some(backoff);
+        }
+
+        let random = RandomState::new().build_hasher().finish();
+        let nanos = backoff.as_nanos() as u64;
+        Some(Duration::from_nanos(random % (nanos + 1)))
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_retryable_error(error: &TransportError) -> bool {
+    matches!(
+        error.kind(),
+        TransportErrorKind::Connect | TransportErrorKind::Timeout | TransportErrorKind::Request
+    )
+}
+
+/// Parses a `Retry-After` header, which is either a number of seconds or an HTTP date.
+pub(crate) fn retry_after(resp: &Response) -> Option<Duration> {
+    let value = resp.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+impl Client {
+    /// Sets the [`RetryPolicy`] used for calls that are safe to replay.
+    ///
+    /// Without a policy, every request is attempted exactly once.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Sends a request that is safe to replay, retrying it according to the
+    /// configured [`RetryPolicy`].
+    pub(crate) async fn send_idempotent(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, TransportError> {
+        let policy = match &self.retry_policy {
+            Some(policy) => policy,
+            None => return self.execute(request).await,
+        };
+
+        let mut retry = 0;
+        loop {
+            let attempt = match request.try_clone() {
+                Some(attempt) => attempt,
+                None => return self.execute(request).await,
+            };
+            retry += 1;
+            let last_attempt = retry >= policy.max_attempts;
+
+            let delay = match self.execute(attempt).await {
+                Ok(resp) if !last_attempt && is_retryable_status(resp.status()) => {
+                    match policy.delay(retry, retry_after(&resp)) {
+                        Some(delay) => {
+                            warn!("gotrue returned {}, retrying", resp.status());
+                            delay
+                        }
+                        None => return Ok(resp),
+                    }
+                }
+                Err(e) if !last_attempt && is_retryable_error(&e) => {
+                    warn!("could not make request to gotrue, retrying: {}", e);
+                    policy.delay(retry, None).unwrap_or_default()
+                }
+                result => return result,
+            };
+
+            debug!("waiting {:?} before attempt {}", delay, retry + 1);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
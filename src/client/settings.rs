@@ -1,34 +1,223 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
+use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 
-use crate::{client::handle_gotrue_resp_status, Client, Error};
+use crate::{client::handle_gotrue_resp, Client, Error};
 
 /// Represents the settings of a GoTrue instances.
-#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Settings {
-    /// The map shows which external providers are enabled.
-    pub external: HashMap<String, bool>,
+    /// Which sign in channels and external providers are enabled.
+    pub external: ExternalProviders,
     /// Whether signup is disabled.
     pub disable_signup: bool,
-    /// Whether autoconfirm is enabled.
+    /// Whether autoconfirm is enabled, as reported by older GoTrue versions.
+    /// Newer versions report [`Settings::mailer_autoconfirm`] instead.
     pub autoconfirm: bool,
+    /// Whether new email addresses are confirmed without sending a confirmation.
+    pub mailer_autoconfirm: bool,
+    /// Whether new phone numbers are confirmed without sending an SMS.
+    pub phone_autoconfirm: bool,
+    /// The SMS provider used to send one-time codes, e.g. `twilio`.
+    pub sms_provider: Option<String>,
+    /// Whether multi-factor authentication is enabled.
+    pub mfa_enabled: bool,
+    /// Whether SAML single sign-on is enabled.
+    pub saml_enabled: bool,
+}
+
+/// The sign in channels and external providers of [`Settings`].
+///
+/// Older GoTrue versions do not report every channel, so those are `None`
+/// rather than assumed to be disabled.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalProviders {
+    /// Whether users can sign in with an email, if the instance says.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+    /// Whether users can sign in with a phone number, if the instance says.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<bool>,
+    /// Whether users can sign in anonymously, if the instance says.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anonymous_users: Option<bool>,
+    /// Which OAuth providers are enabled, by name.
+    #[serde(flatten)]
+    pub oauth: HashMap<String, bool>,
+}
+
+/// An OAuth provider GoTrue can sign users in with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Provider {
+    /// Apple.
+    Apple,
+    /// Azure.
+    Azure,
+    /// Bitbucket.
+    Bitbucket,
+    /// Discord.
+    Discord,
+    /// Facebook.
+    Facebook,
+    /// Figma.
+    Figma,
+    /// Fly.io.
+    Fly,
+    /// GitHub.
+    Github,
+    /// GitLab.
+    Gitlab,
+    /// Google.
+    Google,
+    /// Kakao.
+    Kakao,
+    /// Keycloak.
+    Keycloak,
+    /// LinkedIn.
+    Linkedin,
+    /// LinkedIn over OpenID Connect.
+    LinkedinOidc,
+    /// Notion.
+    Notion,
+    /// Slack.
+    Slack,
+    /// Slack over OpenID Connect.
+    SlackOidc,
+    /// Spotify.
+    Spotify,
+    /// Twitch.
+    Twitch,
+    /// Twitter.
+    Twitter,
+    /// WorkOS.
+    Workos,
+    /// Zoom.
+    Zoom,
+    /// A provider this crate does not know about, by name.
+    Other(String),
+}
+
+impl Provider {
+    /// The name GoTrue uses for the provider.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Provider::Apple => "apple",
+            Provider::Azure => "azure",
+            Provider::Bitbucket => "bitbucket",
+            Provider::Discord => "discord",
+            Provider::Facebook => "facebook",
+            Provider::Figma => "figma",
+            Provider::Fly => "fly",
+            Provider::Github => "github",
+            Provider::Gitlab => "gitlab",
+            Provider::Google => "google",
+            Provider::Kakao => "kakao",
+            Provider::Keycloak => "keycloak",
+            Provider::Linkedin => "linkedin",
+            Provider::LinkedinOidc => "linkedin_oidc",
+            Provider::Notion => "notion",
+            Provider::Slack => "slack",
+            Provider::SlackOidc => "slack_oidc",
+            Provider::Spotify => "spotify",
+            Provider::Twitch => "twitch",
+            Provider::Twitter => "twitter",
+            Provider::Workos => "workos",
+            Provider::Zoom => "zoom",
+            Provider::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for Provider {
+    /// The provider of a name, ignoring case.
+    fn from(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "apple" => Provider::Apple,
+            "azure" => Provider::Azure,
+            "bitbucket" => Provider::Bitbucket,
+            "discord" => Provider::Discord,
+            "facebook" => Provider::Facebook,
+            "figma" => Provider::Figma,
+            "fly" => Provider::Fly,
+            "github" => Provider::Github,
+            "gitlab" => Provider::Gitlab,
+            "google" => Provider::Google,
+            "kakao" => Provider::Kakao,
+            "keycloak" => Provider::Keycloak,
+            "linkedin" => Provider::Linkedin,
+            "linkedin_oidc" => Provider::LinkedinOidc,
+            "notion" => Provider::Notion,
+            "slack" => Provider::Slack,
+            "slack_oidc" => Provider::SlackOidc,
+            "spotify" => Provider::Spotify,
+            "twitch" => Provider::Twitch,
+            "twitter" => Provider::Twitter,
+            "workos" => Provider::Workos,
+            "zoom" => Provider::Zoom,
+            other => Provider::Other(other.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Settings {
+    /// Whether users can sign in with the OAuth provider, or `None` if the
+    /// instance does not list the provider.
+    pub fn supports_provider(&self, provider: Provider) -> Option<bool> {
+        self.external.oauth.get(provider.as_str()).copied()
+    }
+
+    /// Whether new users can sign up with an email, or `None` if the
+    /// instance does not say whether emails are enabled.
+    pub fn can_sign_up_with_email(&self) -> Option<bool> {
+        self.can_sign_up_with(self.external.email)
+    }
+
+    /// Whether new users can sign up with a phone number, or `None` if the
+    /// instance does not say whether phones are enabled.
+    pub fn can_sign_up_with_phone(&self) -> Option<bool> {
+        self.can_sign_up_with(self.external.phone)
+    }
+
+    /// Whether users can sign in anonymously, or `None` if the instance does
+    /// not say whether anonymous users are enabled.
+    pub fn can_sign_in_anonymously(&self) -> Option<bool> {
+        self.can_sign_up_with(self.external.anonymous_users)
+    }
+
+    fn can_sign_up_with(&self, channel: Option<bool>) -> Option<bool> {
+        if self.disable_signup {
+            return Some(false);
+        }
+        channel
+    }
+
+    /// Whether new email addresses are confirmed without sending a confirmation,
+    /// on older and newer GoTrue versions alike.
+    pub fn autoconfirms_email(&self) -> bool {
+        self.mailer_autoconfirm || self.autoconfirm
+    }
 }
 
 impl Client {
     /// Get the publicly available settings for the GoTrue instance.
+    ///
+    /// The settings are also stored in the [`crate::SettingsCache`], if one is set.
     pub async fn get_settings(&self) -> Result<Settings, Error> {
         let endpoint = format!("{}/settings", self.url);
 
         debug!("calling {}", endpoint);
-        let resp = match self
-            .client
-            .get(endpoint)
-            .headers(self.headers.clone())
-            .send()
-            .await
-        {
+        let request = self.get(endpoint).headers(self.headers.clone());
+        let resp = match self.send_idempotent(request).await {
             Ok(resp) => resp,
             Err(e) => {
                 error!("could not make request to gotrue: {}", e);
@@ -36,10 +225,7 @@ impl Client {
             }
         };
 
-        if let Err(e) = handle_gotrue_resp_status(resp.status()) {
-            error!("gotrue returned an error status: {}", resp.status());
-            return Err(e);
-        }
+        let resp = handle_gotrue_resp(resp).await?;
 
         let settings = match resp.json::<Settings>().await {
             Ok(settings) => settings,
@@ -49,6 +235,10 @@ impl Client {
             }
         };
 
+        if let Some(cache) = &self.settings_cache {
+            cache.store(&settings);
+        }
+
         Ok(settings)
     }
 }
@@ -0,0 +1,84 @@
+use serde::Serialize;
+use tracing::{debug, error};
+
+use crate::{Client, Error};
+
+use super::handle_gotrue_resp;
+
+/// Which sessions [`Client::sign_out_with_scope`] ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignOutScope {
+    /// Every session of the user, on every device.
+    #[default]
+    Global,
+    /// Only the session of the access token.
+    Local,
+    /// Every session of the user except the one of the access token.
+    Others,
+}
+
+impl Client {
+    /// Signs the user of the access token out of the sessions of `scope`.
+    ///
+    /// The refresh tokens of those sessions are revoked, but their access tokens
+    /// stay valid until they expire.
+    ///
+    /// The client keeps no session state to clear: [`crate::Session`]s are
+    /// handed to the caller, who should drop theirs once this succeeds, unless
+    /// the scope is [`SignOutScope::Others`].
+    ///
+    /// GoTrue has no admin endpoint to revoke the sessions of another user. To
+    /// cut a user off, ban them with [`Client::update_user_by_id`] and
+    /// [`crate::AdminUserAttributes::ban_duration`], which stops their refresh
+    /// tokens from being used.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, EmailOrPhone, SignOutScope};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let email = "email@example.com".to_string();
+    ///     let session = client.sign_in(EmailOrPhone::Email(email), "Abcd1234!").await?;
+    ///
+    ///     // Sign out every other device, staying signed in on this one.
+    ///     client
+    ///         .sign_out_with_scope(&session.access_token, SignOutScope::Others)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sign_out_with_scope(
+        &self,
+        access_token: &str,
+        scope: SignOutScope,
+    ) -> Result<(), Error> {
+        let endpoint = format!("{}/logout", self.url);
+
+        debug!("calling {}", endpoint);
+        let resp = match self
+            .execute(
+                self.post(endpoint)
+                    .headers(self.authorized_headers(access_token))
+                    .query(&[("scope", scope)]),
+            )
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        handle_gotrue_resp(resp).await?;
+
+        Ok(())
+    }
+}
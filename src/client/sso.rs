@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::debug;
+
+use crate::{
+    session::Session,
+    sso_provider::{SsoProvider, SsoProviderAttributes},
+    Client, Error,
+};
+
+use super::gotrue_json;
+
+/// The identity provider to sign in with through [`Client::sign_in_with_sso`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SsoTarget {
+    /// The email domain of the user, e.g. `example.com`.
+    Domain(String),
+    /// The id of a registered [`SsoProvider`].
+    ProviderId(String),
+}
+
+/// How a PKCE code challenge was derived from its verifier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeChallengeMethod {
+    /// The challenge is the base64url encoded SHA-256 hash of the verifier.
+    #[default]
+    S256,
+    /// The challenge is the verifier itself.
+    Plain,
+}
+
+/// A PKCE code challenge, whose verifier is later passed to
+/// [`Client::exchange_code_for_session`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkceChallenge {
+    /// The code challenge.
+    pub challenge: String,
+    /// How the challenge was derived from the verifier.
+    pub method: CodeChallengeMethod,
+}
+
+/// Options for [`Client::sign_in_with_sso`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SsoOptions {
+    /// Where to send the user after they signed in. Defaults to the site URL.
+    pub redirect_to: Option<String>,
+    /// The PKCE challenge, to receive a code instead of a session in the redirect.
+    pub pkce: Option<PkceChallenge>,
+    /// The captcha token, if captcha protection is enabled.
+    pub captcha_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SsoRedirect {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct SsoProviderList {
+    items: Vec<SsoProvider>,
+}
+
+impl Client {
+    /// Starts an enterprise (SAML) SSO sign-in, returning the URL of the identity
+    /// provider to send the user to.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, SsoOptions, SsoTarget};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let options = SsoOptions {
+    ///         redirect_to: Some("https://example.com/welcome".to_string()),
+    ///         ..SsoOptions::default()
+    ///     };
+    ///     let url = client
+    ///         .sign_in_with_sso(SsoTarget::Domain("example.com".to_string()), &options)
+    ///         .await?;
+    ///
+    ///     println!("redirect the user to {}", url);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sign_in_with_sso(
+        &self,
+        target: SsoTarget,
+        options: &SsoOptions,
+    ) -> Result<String, Error> {
+        let endpoint = format!("{}/sso", self.url);
+
+        let mut body = match target {
+            SsoTarget::Domain(domain) => json!({ "domain": domain }),
+            SsoTarget::ProviderId(provider_id) => json!({ "provider_id": provider_id }),
+        };
+        body["skip_http_redirect"] = json!(true);
+        if let Some(redirect_to) = &options.redirect_to {
+            body["redirect_to"] = json!(redirect_to);
+        }
+        if let Some(pkce) = &options.pkce {
+            body["code_challenge"] = json!(pkce.challenge);
+            body["code_challenge_method"] = json!(pkce.method);
+        }
+        if let Some(captcha_token) = &options.captcha_token {
+            body["gotrue_meta_security"] = json!({ "captcha_token": captcha_token });
+        }
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .post(endpoint)
+            .headers(self.headers.clone())
+            .json(&body);
+
+        let redirect: SsoRedirect = gotrue_json(self.execute(request).await).await?;
+        Ok(redirect.url)
+    }
+
+    /// Exchanges the code of a PKCE flow for a session.
+    ///
+    /// Fails with [`Error::WrongToken`] if the code or the verifier is invalid.
+    pub async fn exchange_code_for_session(
+        &self,
+        auth_code: &str,
+        code_verifier: &str,
+    ) -> Result<Session, Error> {
+        let endpoint = format!("{}/token?grant_type=pkce", self.url);
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .post(endpoint)
+            .headers(self.headers.clone())
+            .json(&json!({
+                "auth_code": auth_code,
+                "code_verifier": code_verifier,
+            }));
+
+        match gotrue_json(self.execute(request).await).await {
+            Err(Error::Api {
+                status: 400 | 404, ..
+            }) => Err(Error::WrongToken),
+            result => result,
+        }
+    }
+
+    /// Lists the registered SSO providers.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    pub async fn list_sso_providers(&self) -> Result<Vec<SsoProvider>, Error> {
+        let endpoint = format!("{}/admin/sso/providers", self.url);
+
+        debug!("calling {}", endpoint);
+        let request = self.get(endpoint).headers(self.headers.clone());
+
+        let list: SsoProviderList = gotrue_json(self.execute(request).await).await?;
+        Ok(list.items)
+    }
+
+    /// Registers a SAML identity provider.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, SsoProviderAttributes};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url).with_header("Authorization", "Bearer service.role.jwt");
+    ///
+    ///     let attributes = SsoProviderAttributes {
+    ///         metadata_url: Some("https://idp.example.com/saml/metadata".to_string()),
+    ///         domains: Some(vec!["example.com".to_string()]),
+    ///         ..SsoProviderAttributes::default()
+    ///     };
+    ///     let provider = client.create_sso_provider(&attributes).await?;
+    ///
+    ///     println!("created {}", provider.id);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_sso_provider(
+        &self,
+        attributes: &SsoProviderAttributes,
+    ) -> Result<SsoProvider, Error> {
+        let endpoint = format!("{}/admin/sso/providers", self.url);
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .post(endpoint)
+            .headers(self.headers.clone())
+            .json(attributes);
+
+        gotrue_json(self.execute(request).await).await
+    }
+
+    /// Gets a registered SSO provider.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    pub async fn get_sso_provider(&self, id: &str) -> Result<SsoProvider, Error> {
+        let endpoint = format!("{}/admin/sso/providers/{}", self.url, id);
+
+        debug!("calling {}", endpoint);
+        let request = self.get(endpoint).headers(self.headers.clone());
+
+        gotrue_json(self.execute(request).await).await
+    }
+
+    /// Updates a registered SSO provider. Only the attributes that are set are changed.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    pub async fn update_sso_provider(
+        &self,
+        id: &str,
+        attributes: &SsoProviderAttributes,
+    ) -> Result<SsoProvider, Error> {
+        let endpoint = format!("{}/admin/sso/providers/{}", self.url, id);
+
+        debug!("calling {}", endpoint);
+        let request = self
+            .put(endpoint)
+            .headers(self.headers.clone())
+            .json(attributes);
+
+        gotrue_json(self.execute(request).await).await
+    }
+
+    /// Removes a registered SSO provider, returning it.
+    ///
+    /// This method requires the service_role or supabase_admin JWT set as an Auth Bearer header.
+    pub async fn delete_sso_provider(&self, id: &str) -> Result<SsoProvider, Error> {
+        let endpoint = format!("{}/admin/sso/providers/{}", self.url, id);
+
+        debug!("calling {}", endpoint);
+        let request = self.delete(endpoint).headers(self.headers.clone());
+
+        gotrue_json(self.execute(request).await).await
+    }
+}
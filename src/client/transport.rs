@@ -0,0 +1,202 @@
+use std::{error::Error as StdError, fmt, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use thiserror::Error;
+
+use crate::{
+    client::{
+        cassette::CassetteMode,
+        request::{RequestBuilder, Response},
+    },
+    Client,
+};
+
+/// A request to GoTrue, with its body already serialized.
+pub type HttpRequest = http::Request<Vec<u8>>;
+
+/// A response from GoTrue, with its body read in full.
+pub type HttpResponse = http::Response<Vec<u8>>;
+
+/// Sends HTTP requests for a [`Client`].
+///
+/// The client builds every request and interprets every response itself, so
+/// a transport only has to move bytes: plug in another HTTP stack, a mock for
+/// unit tests, or a wrapper adding metrics or tracing. Set one with
+/// [`Client::with_transport`].
+///
+/// A transport must not follow redirects, since some GoTrue endpoints answer
+/// with one the client reads. Failures to get any response are reported as a
+/// [`TransportError`]; error statuses are normal responses.
+///
+/// # Example
+///
+/// ```
+/// use futures_util::future::BoxFuture;
+/// use go_true_redux::{http, Client, HttpRequest, HttpResponse, HttpTransport, TransportError};
+///
+/// /// Answers every request with the same settings.
+/// #[derive(Debug)]
+/// struct FixedSettings;
+///
+/// impl HttpTransport for FixedSettings {
+///     fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+///         let body = r#"{"external":{},"disable_signup":false,"autoconfirm":true}"#;
+///         Box::pin(async move { Ok(http::Response::new(body.as_bytes().to_vec())) })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::with_transport("http://localhost:9998", FixedSettings);
+///     let settings = client.get_settings().await?;
+///
+///     assert!(settings.autoconfirm);
+///     Ok(())
+/// }
+/// ```
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    /// Sends the request and reads the whole response.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        (**self).send(request)
+    }
+}
+
+/// Why a [`TransportError`] happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransportErrorKind {
+    /// The connection to GoTrue could not be made.
+    Connect,
+    /// GoTrue did not answer in time.
+    Timeout,
+    /// The request could not be sent.
+    Request,
+    /// Any other failure, such as an unreadable response.
+    Other,
+}
+
+/// A request that got no response from GoTrue.
+///
+/// Connection failures, timeouts and failures to send the request are retried
+/// by a [`RetryPolicy`](crate::RetryPolicy).
+#[derive(Debug, Error)]
+#[error("{source}")]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl TransportError {
+    /// Creates an error of the given kind.
+    pub fn new(
+        kind: TransportErrorKind,
+        source: impl Into<Box<dyn StdError + Send + Sync>>,
+    ) -> Self {
+        TransportError {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Why the error happened.
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+}
+
+/// The default transport, sending requests with [`reqwest`].
+///
+/// Enabled by the `reqwest-transport` feature, which is on by default.
+#[cfg(feature = "reqwest-transport")]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest-transport")]
+impl ReqwestTransport {
+    /// A transport with reqwest's defaults, except that redirects are not followed.
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("could not build the http client");
+
+        ReqwestTransport { client }
+    }
+
+    /// A transport sending with the given client, which must not follow redirects.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "reqwest-transport")]
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        ReqwestTransport::new()
+    }
+}
+
+#[cfg(feature = "reqwest-transport")]
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        Box::pin(async move {
+            let request = reqwest::Request::try_from(request)
+                .map_err(|e| TransportError::new(TransportErrorKind::Request, e))?;
+            let resp = self.client.execute(request).await.map_err(from_reqwest)?;
+
+            let mut response = http::Response::builder()
+                .status(resp.status())
+                .version(resp.version());
+            if let Some(headers) = response.headers_mut() {
+                *headers = resp.headers().clone();
+            }
+            let body = resp.bytes().await.map_err(from_reqwest)?;
+
+            response
+                .body(body.to_vec())
+                .map_err(|e| TransportError::new(TransportErrorKind::Other, e))
+        })
+    }
+}
+
+#[cfg(feature = "reqwest-transport")]
+fn from_reqwest(error: reqwest::Error) -> TransportError {
+    let kind = if error.is_connect() {
+        TransportErrorKind::Connect
+    } else if error.is_timeout() {
+        TransportErrorKind::Timeout
+    } else if error.is_request() {
+        TransportErrorKind::Request
+    } else {
+        TransportErrorKind::Other
+    };
+
+    TransportError::new(kind, error)
+}
+
+impl Client {
+    /// Sends a request. Every call to GoTrue goes through here, so it can be
+    /// recorded or replayed.
+    pub(crate) async fn execute(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, TransportError> {
+        let (request, url) = request.build()?;
+
+        let response = match &self.cassette {
+            None => self.transport.send(request).await?,
+            Some(cassette) => match cassette.mode() {
+                CassetteMode::Replay => cassette.play(&request),
+                CassetteMode::Record => cassette.record_exchange(&*self.transport, request).await?,
+            },
+        };
+
+        Ok(Response::new(url, response))
+    }
+}
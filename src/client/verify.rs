@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+
+use http::header::LOCATION;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, error};
+use url::Url;
+
+use crate::{session::Session, Client, Error};
+
+use super::{handle_gotrue_resp, request::Response};
+
+/// The kind of token being verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationType {
+    /// Confirms the email of a new user.
+    Signup,
+    /// Signs a user in to reset their password.
+    Recovery,
+    /// Accepts an invitation.
+    Invite,
+    /// Signs a user in with a magic link.
+    #[serde(rename = "magiclink")]
+    MagicLink,
+    /// Confirms a change of email address.
+    EmailChange,
+    /// Signs a user in with an SMS OTP.
+    Sms,
+    /// Confirms a change of phone number.
+    PhoneChange,
+    /// Signs a user in with an email OTP.
+    Email,
+}
+
+/// A token to verify with [`Client::verify_otp`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyParams {
+    /// The OTP sent to confirm the email of a new user.
+    Signup {
+        /// The email of the user.
+        email: String,
+        /// The OTP from the confirmation email.
+        token: String,
+    },
+    /// The OTP sent to reset a password.
+    Recovery {
+        /// The email of the user.
+        email: String,
+        /// The OTP from the recovery email.
+        token: String,
+    },
+    /// The OTP sent with an invitation.
+    Invite {
+        /// The email of the invited user.
+        email: String,
+        /// The OTP from the invitation email.
+        token: String,
+    },
+    /// The OTP sent with a magic link.
+    MagicLink {
+        /// The email of the user.
+        email: String,
+        /// The OTP from the magic link email.
+        token: String,
+    },
+    /// The OTP sent to confirm a change of email address.
+    EmailChange {
+        /// The email address the OTP was sent to.
+        email: String,
+        /// The OTP from the email.
+        token: String,
+    },
+    /// The OTP sent by SMS to sign in or confirm a phone number.
+    Sms {
+        /// The phone number of the user.
+        phone: String,
+        /// The OTP from the SMS.
+        token: String,
+    },
+    /// The OTP sent by SMS to confirm a change of phone number.
+    PhoneChange {
+        /// The new phone number.
+        phone: String,
+        /// The OTP from the SMS.
+        token: String,
+    },
+    /// The hashed token of an email link, as found in its `token` query parameter.
+    TokenHash {
+        /// The `type` query parameter of the link.
+        verification_type: VerificationType,
+        /// The `token` query parameter of the link.
+        token_hash: String,
+    },
+}
+
+impl VerifyParams {
+    /// The kind of token being verified.
+    pub fn verification_type(&self) -> VerificationType {
+        match self {
+            VerifyParams::Signup { .. } => VerificationType::Signup,
+            VerifyParams::Recovery { .. } => VerificationType::Recovery,
+            VerifyParams::Invite { .. } => VerificationType::Invite,
+            VerifyParams::MagicLink { .. } => VerificationType::MagicLink,
+            VerifyParams::EmailChange { .. } => VerificationType::EmailChange,
+            VerifyParams::Sms { .. } => VerificationType::Sms,
+            VerifyParams::PhoneChange { .. } => VerificationType::PhoneChange,
+            VerifyParams::TokenHash {
+                verification_type, ..
+            } => *verification_type,
+        }
+    }
+
+    fn to_body(&self) -> Value {
+        let verification_type = self.verification_type();
+        match self {
+            VerifyParams::Signup { email, token }
+            | VerifyParams::Recovery { email, token }
+            | VerifyParams::Invite { email, token }
+            | VerifyParams::MagicLink { email, token }
+            | VerifyParams::EmailChange { email, token } => json!({
+                "type": verification_type,
+                "email": email,
+                "token": token,
+            }),
+            VerifyParams::Sms { phone, token } | VerifyParams::PhoneChange { phone, token } => {
+                json!({
+                    "type": verification_type,
+                    "phone": phone,
+                    "token": token,
+                })
+            }
+            VerifyParams::TokenHash { token_hash, .. } => json!({
+                "type": verification_type,
+                "token_hash": token_hash,
+            }),
+        }
+    }
+}
+
+/// A successful verification.
+#[derive(Debug, Clone)]
+pub struct Verification {
+    /// The session of the verified user.
+    pub session: Session,
+    /// The kind of token that was verified.
+    pub verification_type: VerificationType,
+}
+
+impl Client {
+    /// Verifies an OTP or a hashed link token, signing the user in.
+    ///
+    /// Fails with [`Error::WrongToken`] if the token is invalid or has expired.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::{Client, VerifyParams};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let params = VerifyParams::Sms {
+    ///         phone: "+15555550100".to_string(),
+    ///         token: "123456".to_string(),
+    ///     };
+    ///     let verification = client.verify_otp(params).await?;
+    ///
+    ///     println!("{}", verification.session.access_token);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify_otp(&self, params: VerifyParams) -> Result<Verification, Error> {
+        let resp = self.send_verify(&params).await?;
+
+        let session = match resp.json::<Session>().await {
+            Ok(session) => session,
+            Err(e) => {
+                error!("could not deserialize the response into a session: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        Ok(Verification {
+            session,
+            verification_type: params.verification_type(),
+        })
+    }
+
+    /// Posts the token to `/verify`, mapping rejected tokens to [`Error::WrongToken`].
+    pub(crate) async fn send_verify(&self, params: &VerifyParams) -> Result<Response, Error> {
+        let endpoint = format!("{}/verify", self.url);
+
+        debug!("calling {}", endpoint);
+        let resp = match self
+            .execute(
+                self.post(endpoint)
+                    .headers(self.headers.clone())
+                    .json(&params.to_body()),
+            )
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        match handle_gotrue_resp(resp).await {
+            Err(Error::NotAuthenticated) | Err(Error::Api { status: 403, .. }) => {
+                Err(Error::WrongToken)
+            }
+            result => result,
+        }
+    }
+
+    /// "Clicks" a GoTrue verification link, such as the one sent in a
+    /// confirmation, invite, magic link or recovery email.
+    ///
+    /// The link (`/verify?token=...&type=...&redirect_to=...`) is requested
+    /// without following its redirect, and the session GoTrue puts in the
+    /// fragment of the `Location` header is returned along with its user.
+    /// Fails with [`Error::WrongToken`] if the link is invalid or has expired.
+    ///
+    /// Only links to this client's GoTrue are followed, since the request
+    /// carries the client's headers. Links to any other host fail with
+    /// [`Error::WrongToken`] without being requested.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use go_true_redux::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let url = "http://localhost:9998";
+    ///     let client = Client::new(url);
+    ///
+    ///     let link = "http://localhost:9998/verify?token=abc&type=magiclink&redirect_to=http://localhost:3000";
+    ///     let verification = client.verify_link(link).await?;
+    ///
+    ///     println!("{}", verification.session.user.email);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify_link(&self, link: &str) -> Result<Verification, Error> {
+        let link = match Url::parse(link) {
+            Ok(link) => link,
+            Err(e) => {
+                error!("could not parse the verification link: {}", e);
+                return Err(Error::WrongToken);
+            }
+        };
+        if !self.is_own_link(&link) {
+            error!(
+                "refusing to follow a verification link to another host: {}",
+                link
+            );
+            return Err(Error::WrongToken);
+        }
+
+        debug!("calling {}", link);
+        let request = self.get(link.clone()).headers(self.headers.clone());
+        let resp = match self.execute(request).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("could not make request to gotrue: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        if !resp.status().is_redirection() {
+            handle_gotrue_resp(resp).await?;
+            error!("gotrue did not redirect from the verification link");
+            return Err(Error::InternalError);
+        }
+
+        let location = resp
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| link.join(location).ok());
+        let location = match location {
+            Some(location) => location,
+            None => {
+                error!("gotrue redirected without a valid location");
+                return Err(Error::InternalError);
+            }
+        };
+
+        // Errors are reported in the fragment by recent versions, in the query by older ones.
+        let params: HashMap<String, String> = location
+            .fragment()
+            .map(|fragment| url::form_urlencoded::parse(fragment.as_bytes()))
+            .into_iter()
+            .flatten()
+            .chain(location.query_pairs())
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        if let Some(error) = params.get("error") {
+            // Older versions put the HTTP status in error_code, newer ones a
+            // string such as otp_expired.
+            let code = params.get("error_code").map(String::as_str);
+            let status = code.and_then(|code| code.parse().ok()).unwrap_or(400);
+            let message = params.get("error_description").unwrap_or(error);
+            error!("gotrue rejected the verification link: {}", message);
+
+            let expired =
+                matches!(code, Some("otp_expired" | "access_denied")) || error == "access_denied";
+
+            return Err(match status {
+                401 | 403 | 410 => Error::WrongToken,
+                _ if expired => Error::WrongToken,
+                _ => Error::Api {
+                    status,
+                    message: message.clone(),
+                },
+            });
+        }
+
+        let (access_token, refresh_token) =
+            match (params.get("access_token"), params.get("refresh_token")) {
+                (Some(access_token), Some(refresh_token)) => {
+                    (access_token.clone(), refresh_token.clone())
+                }
+                _ => {
+                    error!("gotrue redirected without a session");
+                    return Err(Error::InternalError);
+                }
+            };
+
+        let link_type = link
+            .query_pairs()
+            .find(|(key, _)| key == "type")
+            .map(|(_, kind)| kind.into_owned());
+        let verification_type = match params
+            .get("type")
+            .or(link_type.as_ref())
+            .and_then(|kind| serde_json::from_value(Value::String(kind.clone())).ok())
+        {
+            Some(verification_type) => verification_type,
+            None => {
+                error!("could not tell the type of the verification link");
+                return Err(Error::InternalError);
+            }
+        };
+
+        let user = match self.get_user(&access_token).await {
+            Ok(user) => user,
+            Err(e) => {
+                error!("could not get the verified user: {}", e);
+                return Err(Error::InternalError);
+            }
+        };
+
+        let session = Session {
+            access_token,
+            token_type: params
+                .get("token_type")
+                .cloned()
+                .unwrap_or_else(|| "bearer".to_owned()),
+            expires_in: params
+                .get("expires_in")
+                .and_then(|expires_in| expires_in.parse().ok())
+                .unwrap_or_default(),
+            refresh_token,
+            user,
+        };
+
+        Ok(Verification {
+            session,
+            verification_type,
+        })
+    }
+
+    /// Whether the link points at this client's GoTrue, under its base path.
+    fn is_own_link(&self, link: &Url) -> bool {
+        match Url::parse(&self.url) {
+            Ok(base) => {
+                link.origin() == base.origin()
+                    && link.path().starts_with(base.path().trim_end_matches('/'))
+            }
+            Err(_) => false,
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::client::preflight::Capability;
+
 ///
 /// Represents a error returned from a user action by GoTrue.
 ///
@@ -33,4 +37,48 @@ pub enum Error {
     /// Internal GoTrue error.
     #[error("GoTrue internal error")]
     InternalError,
+
+    /// The user must reauthenticate with a nonce from [`crate::Client::reauthenticate`]
+    /// before changing their password.
+    #[error("Reauthentication required.")]
+    ReauthenticationRequired,
+
+    /// The user has no pending email or phone change to confirm.
+    #[error("No pending change.")]
+    NoPendingChange,
+
+    /// Too many requests were made for this action in a short period.
+    #[error("Rate limited, retry after {retry_after:?}.")]
+    RateLimited {
+        /// How long to wait before trying again, if GoTrue said so.
+        retry_after: Option<Duration>,
+    },
+
+    /// GoTrue did not become healthy in time, see [`crate::Client::wait_until_ready`].
+    #[error("GoTrue was not ready after {waited:?}.")]
+    NotReady {
+        /// How long GoTrue was waited for.
+        waited: Duration,
+    },
+
+    /// The instance has disabled what the call needs, as shown by a
+    /// [`crate::SettingsCache`].
+    #[error("GoTrue has {capability} disabled.")]
+    Disabled {
+        /// What is disabled.
+        capability: Capability,
+    },
+
+    /// Writing to or reading from an I/O resource failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// GoTrue returned an error that is not covered by another variant.
+    #[error("GoTrue returned {status}: {message}")]
+    Api {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The error message returned by GoTrue.
+        message: String,
+    },
 }
@@ -36,17 +36,56 @@
 //! [gotrue]: https://github.com/supabase/gotrue
 //! [readme]: https://github.com/fubinator/gotrue-rs
 
+mod admin_user_attributes;
+mod audit_log;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 mod error;
 mod session;
+mod sso_provider;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod user;
 mod user_attributes;
 mod user_list;
-mod user_update;
 
-pub use client::settings::Settings;
+pub use admin_user_attributes::AdminUserAttributes;
+pub use audit_log::{AuditActor, AuditLogEntry, AuditLogPage};
+pub use client::audit::{AuditLogFilter, AuditLogQuery};
+pub use client::cassette::{Cassette, CassetteMode};
+pub use client::export_users::ExportFormat;
+pub use client::generate_link::{GenerateLinkOptions, GenerateLinkParams, GeneratedLink};
+pub use client::health::Health;
+pub use client::id_token::IdTokenCredentials;
+pub use client::import_users::{ImportOutcome, ImportRecord, ImportReport};
+pub use client::list_users::{ListUsersQuery, SortDirection};
+pub use client::magic_link::{EmailOtpDelivery, MagicLinkOptions};
+pub use client::otp::{OtpChannel, OtpOptions};
+pub use client::pending_change::EmailChangeConfirmation;
+pub use client::preflight::{Capability, SettingsCache};
+pub use client::rate_limit::RateLimitTracker;
+pub use client::resend::{ResendOptions, ResendParams};
+pub use client::retry::RetryPolicy;
+pub use client::settings::{ExternalProviders, Provider, Settings};
+pub use client::sign_out::SignOutScope;
+pub use client::sso::{CodeChallengeMethod, PkceChallenge, SsoOptions, SsoTarget};
+#[cfg(feature = "reqwest-transport")]
+pub use client::transport::ReqwestTransport;
+pub use client::transport::{
+    HttpRequest, HttpResponse, HttpTransport, TransportError, TransportErrorKind,
+};
+pub use client::verify::{Verification, VerificationType, VerifyParams};
 pub use client::Client;
 pub use client::EmailOrPhone;
 pub use error::Error;
+/// The HTTP types [`HttpTransport`] works with.
+pub use http;
+pub use session::Session;
+pub use sso_provider::{
+    AttributeMapping, AttributeMappingValue, SamlProvider, SsoDomain, SsoProvider,
+    SsoProviderAttributes,
+};
 pub use user::User;
 pub use user_attributes::UserAttributes;
+pub use user_list::UserList;
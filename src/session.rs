@@ -2,11 +2,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::user::User;
 
+/// Represents the session of a signed in user.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
+    /// The JWT to authenticate the user with.
     pub access_token: String,
+    /// The type of the access token, usually `bearer`.
     pub token_type: String,
+    /// The number of seconds until the access token expires.
     pub expires_in: i32,
+    /// The token used to get a new session once the access token expires.
     pub refresh_token: String,
+    /// The signed in user.
     pub user: User,
 }
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Represents an enterprise SSO identity provider registered in GoTrue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SsoProvider {
+    /// The id of the provider, used to sign in with [`crate::SsoTarget::ProviderId`].
+    pub id: String,
+    /// The SAML configuration of the provider.
+    #[serde(default)]
+    pub saml: Option<SamlProvider>,
+    /// The email domains whose users sign in through this provider.
+    #[serde(default)]
+    pub domains: Vec<SsoDomain>,
+    /// The date the provider was created.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The date the provider was last updated.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// The SAML configuration of an [`SsoProvider`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamlProvider {
+    /// The entity id of the identity provider, read from its metadata.
+    pub entity_id: String,
+    /// The URL the metadata is fetched from, if it was registered by URL.
+    #[serde(default)]
+    pub metadata_url: Option<String>,
+    /// The metadata XML of the identity provider.
+    #[serde(default)]
+    pub metadata_xml: Option<String>,
+    /// How SAML assertion attributes map to user metadata.
+    #[serde(default)]
+    pub attribute_mapping: Option<AttributeMapping>,
+}
+
+/// An email domain routed to an [`SsoProvider`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SsoDomain {
+    /// The domain, e.g. `example.com`.
+    pub domain: String,
+    /// The date the domain was added.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The date the domain was last updated.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// Maps SAML assertion attributes to keys of the user metadata.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttributeMapping {
+    /// The mapping of each user metadata key.
+    #[serde(default)]
+    pub keys: HashMap<String, AttributeMappingValue>,
+}
+
+/// Where a user metadata key of an [`AttributeMapping`] is read from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttributeMappingValue {
+    /// The name of the SAML attribute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Several SAML attribute names, the first one present is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub names: Option<Vec<String>>,
+    /// The value used when the assertion has none of the attributes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+}
+
+///
+/// Represents attributes used by admins to create or update an [`SsoProvider`].
+///
+/// Exactly one of `metadata_url` or `metadata_xml` is required to create a provider.
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SsoProviderAttributes {
+    /// The type of the provider. Only `saml` is supported by GoTrue.
+    #[serde(rename = "type")]
+    pub provider_type: String,
+    /// The URL to fetch the metadata of the identity provider from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_url: Option<String>,
+    /// The metadata XML of the identity provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_xml: Option<String>,
+    /// How SAML assertion attributes map to user metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute_mapping: Option<AttributeMapping>,
+    /// The email domains whose users sign in through this provider. Replaces
+    /// the existing domains on update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domains: Option<Vec<String>>,
+}
+
+impl Default for SsoProviderAttributes {
+    fn default() -> Self {
+        SsoProviderAttributes {
+            provider_type: "saml".to_owned(),
+            metadata_url: None,
+            metadata_xml: None,
+            attribute_mapping: None,
+            domains: None,
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! An in-memory fake GoTrue server for hermetic tests.
+//!
+//! Enabled by the `test-util` feature. The server keeps users, sessions and
+//! one-time codes in memory and listens on an ephemeral localhost port, so
+//! tests can run in parallel without the docker-compose stack.
+//!
+//! # Example
+//!
+//! ```
+//! use go_true_redux::{test_util::MockGoTrue, EmailOrPhone, VerifyParams};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let server = MockGoTrue::start().await?;
+//!     let client = server.client();
+//!
+//!     let email = "email@example.com".to_string();
+//!     client.send_otp(EmailOrPhone::Email(email.clone()), None).await?;
+//!
+//!     let token = server.otp(&EmailOrPhone::Email(email.clone())).unwrap();
+//!     let verification = client
+//!         .verify_otp(VerifyParams::MagicLink { email, token })
+//!         .await?;
+//!
+//!     assert_eq!(verification.session.user.email, "email@example.com");
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod state;
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    io,
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    body,
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use tokio::sync::oneshot;
+use tracing::error;
+
+use crate::{client::settings::Settings, user::User, Client, EmailOrPhone, Error};
+
+use state::{Call, State};
+
+/// The bearer token admin endpoints of [`MockGoTrue`] expect.
+const SERVICE_ROLE_KEY: &str = "test-util-service-role-key";
+
+/// An in-memory fake GoTrue server, shut down when dropped.
+///
+/// It serves sign-up, the password and refresh token grants, OTPs and magic
+/// links, verification, the current user, sign-out, settings, health and the
+/// admin user endpoints. Codes that GoTrue would email or text are kept and
+/// can be read with [`MockGoTrue::otp`]. Sign-ups are confirmed right away
+/// unless `mailer_autoconfirm` or `phone_autoconfirm` is turned off with
+/// [`MockGoTrue::set_settings`]. A `password_hash` given to the admin
+/// endpoints is stored as is, and since the fake cannot check it, the user can
+/// only sign in with a password once one is set in its place.
+#[derive(Debug)]
+pub struct MockGoTrue {
+    url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockGoTrue {
+    /// Starts the server on an ephemeral localhost port.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub async fn start() -> Result<Self, Error> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        listener.set_nonblocking(true)?;
+        let url = format!("http://{}", listener.local_addr()?);
+
+        let state = Arc::new(Mutex::new(State::new(
+            url.clone(),
+            SERVICE_ROLE_KEY.to_owned(),
+        )));
+
+        let shared = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = shared.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+
+        let server = Server::from_tcp(listener)
+            .map_err(io::Error::other)?
+            .serve(make_service);
+
+        let (shutdown, signal) = oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let server = server.with_graceful_shutdown(async {
+                let _ = signal.await;
+            });
+            if let Err(e) = server.await {
+                error!("the mock gotrue server failed: {}", e);
+            }
+        });
+
+        Ok(MockGoTrue {
+            url,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The base URL of the server, to pass to [`Client::new`].
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A client pointed at the server.
+    pub fn client(&self) -> Client {
+        Client::new(&self.url)
+    }
+
+    /// A client pointed at the server, authorized for the admin endpoints.
+    pub fn admin_client(&self) -> Client {
+        self.client()
+            .with_header("Authorization", format!("Bearer {SERVICE_ROLE_KEY}"))
+    }
+
+    /// The bearer token the admin endpoints expect.
+    pub fn service_role_key(&self) -> &str {
+        SERVICE_ROLE_KEY
+    }
+
+    /// The last code sent to the address, as GoTrue would have emailed or texted it.
+    ///
+    /// Codes are sent by sign-ups without autoconfirm, OTPs, magic links,
+    /// recoveries, resends and email or phone changes, and are consumed by
+    /// a successful verification.
+    pub fn otp(&self, email_or_phone: &EmailOrPhone) -> Option<String> {
+        let address = match email_or_phone {
+            EmailOrPhone::Email(email) => email,
+            EmailOrPhone::Phone(phone) => phone,
+        };
+        self.lock().code(address)
+    }
+
+    /// The password hash imported for the user with this email or phone number, if any.
+    pub fn password_hash(&self, email_or_phone: &EmailOrPhone) -> Option<String> {
+        let address = match email_or_phone {
+            EmailOrPhone::Email(email) => email,
+            EmailOrPhone::Phone(phone) => phone,
+        };
+        self.lock().password_hash(address)
+    }
+
+    /// Every user of the server, in creation order.
+    pub fn users(&self) -> Vec<User> {
+        self.lock().users()
+    }
+
+    /// The settings served by `/settings`, which also decide whether sign-ups
+    /// are allowed and confirmed right away.
+    pub fn settings(&self) -> Settings {
+        self.lock().settings.clone()
+    }
+
+    /// Replaces the settings of the server.
+    pub fn set_settings(&self, settings: Settings) {
+        self.lock().settings = settings;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panicking test may poison the lock, the state is still usable.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockGoTrue {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+
+    let bytes = body::to_bytes(body).await.unwrap_or_default();
+    let body = serde_json::from_slice(&bytes).unwrap_or_default();
+    let query: HashMap<String, String> = parts
+        .uri
+        .query()
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let bearer = parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let call = Call {
+        method: parts.method.as_str(),
+        path: parts.uri.path(),
+        query,
+        bearer,
+        body,
+    };
+    let reply = state.lock().unwrap_or_else(|e| e.into_inner()).route(&call);
+
+    let mut response = match reply.body {
+        Some(body) => {
+            let mut response = Response::new(Body::from(body.to_string()));
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            response
+        }
+        None => Response::new(Body::empty()),
+    };
+    *response.status_mut() = StatusCode::from_u16(reply.status).unwrap_or_default();
+    for (name, value) in reply.headers {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,678 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::BuildHasher,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    client::settings::{ExternalProviders, Settings},
+    session::Session,
+    user::User,
+};
+
+/// The number of users per page when `per_page` is not set, as in GoTrue.
+const DEFAULT_PER_PAGE: usize = 50;
+
+/// A response of the fake server.
+pub(crate) struct Reply {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Option<Value>,
+}
+
+impl Reply {
+    fn json(status: u16, body: Value) -> Self {
+        Reply {
+            status,
+            headers: Vec::new(),
+            body: Some(body),
+        }
+    }
+
+    fn no_content() -> Self {
+        Reply {
+            status: 204,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// An error in the `{code, msg}` shape of most GoTrue endpoints.
+    fn error(status: u16, msg: &str) -> Self {
+        Reply::json(status, json!({ "code": status, "msg": msg }))
+    }
+
+    /// An error in the OAuth2 shape of the token endpoint.
+    fn grant_error(description: &str) -> Self {
+        Reply::json(
+            400,
+            json!({ "error": "invalid_grant", "error_description": description }),
+        )
+    }
+}
+
+/// A request to the fake server.
+pub(crate) struct Call<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub query: HashMap<String, String>,
+    pub bearer: Option<&'a str>,
+    pub body: Value,
+}
+
+impl Call<'_> {
+    fn str(&self, key: &str) -> Option<&str> {
+        self.body.get(key).and_then(Value::as_str)
+    }
+}
+
+struct Account {
+    user: User,
+    password: Option<String>,
+    /// A hash imported with `password_hash`, kept as is since the fake cannot
+    /// check bcrypt hashes. Password sign-ins are refused while it is set.
+    password_hash: Option<String>,
+}
+
+struct FakeSession {
+    access_token: String,
+    refresh_token: String,
+    user_id: String,
+}
+
+/// The users, sessions and codes of the fake server.
+pub(crate) struct State {
+    pub url: String,
+    pub service_role_key: String,
+    pub settings: Settings,
+    accounts: Vec<Account>,
+    sessions: Vec<FakeSession>,
+    codes: HashMap<String, String>,
+    counter: u64,
+    random: RandomState,
+}
+
+impl std::fmt::Debug for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("State").field("url", &self.url).finish()
+    }
+}
+
+/// Formats the current time as RFC 3339, in UTC.
+fn now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+
+    // Converts days since the epoch to a civil date.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Merges the keys of `patch` into `target`.
+fn merge(target: &mut Option<Value>, patch: &Value) {
+    match (target.as_mut(), patch) {
+        (Some(Value::Object(target)), Value::Object(patch)) => {
+            for (key, value) in patch {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+        (_, Value::Object(_)) => *target = Some(patch.clone()),
+        _ => {}
+    }
+}
+
+impl State {
+    pub fn new(url: String, service_role_key: String) -> Self {
+        State {
+            url,
+            service_role_key,
+            settings: Settings {
+                external: ExternalProviders {
+                    email: Some(true),
+                    phone: Some(true),
+                    anonymous_users: Some(false),
+                    ..Default::default()
+                },
+                mailer_autoconfirm: true,
+                phone_autoconfirm: true,
+                ..Default::default()
+            },
+            accounts: Vec::new(),
+            sessions: Vec::new(),
+            codes: HashMap::new(),
+            counter: 0,
+            random: RandomState::new(),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.counter += 1;
+        self.counter
+    }
+
+    fn token(&mut self, kind: &str) -> String {
+        let n = self.next();
+        format!("fake-{kind}-{n}-{:016x}", self.random.hash_one(n))
+    }
+
+    /// Generates a 6-digit code for the address, replacing any previous one.
+    fn send_code(&mut self, address: &str) {
+        let n = self.next();
+        let code = format!("{:06}", self.random.hash_one((address, n)) % 1_000_000);
+        self.codes.insert(address.to_owned(), code);
+    }
+
+    pub fn code(&self, address: &str) -> Option<String> {
+        self.codes.get(address).cloned()
+    }
+
+    pub fn password_hash(&self, address: &str) -> Option<String> {
+        self.find(Some(address), Some(address))
+            .and_then(|index| self.accounts[index].password_hash.clone())
+    }
+
+    pub fn users(&self) -> Vec<User> {
+        self.accounts
+            .iter()
+            .map(|account| account.user.clone())
+            .collect()
+    }
+
+    fn find(&self, email: Option<&str>, phone: Option<&str>) -> Option<usize> {
+        self.accounts.iter().position(|account| {
+            email.is_some_and(|email| account.user.email == email)
+                || phone.is_some_and(|phone| account.user.phone == phone)
+        })
+    }
+
+    fn find_id(&self, id: &str) -> Option<usize> {
+        self.accounts
+            .iter()
+            .position(|account| account.user.id == id)
+    }
+
+    fn create(&mut self, email: Option<&str>, phone: Option<&str>) -> usize {
+        let n = self.next();
+        let created_at = now();
+        self.accounts.push(Account {
+            user: User {
+                id: format!("00000000-0000-4000-8000-{n:012}"),
+                email: email.unwrap_or_default().to_owned(),
+                phone: phone.unwrap_or_default().to_owned(),
+                aud: "authenticated".to_owned(),
+                role: "authenticated".to_owned(),
+                created_at: created_at.clone(),
+                updated_at: created_at,
+                user_metadata: Some(json!({})),
+                app_metadata: Some(json!({
+                    "provider": if email.is_some() { "email" } else { "phone" },
+                })),
+                ..User::default()
+            },
+            password: None,
+            password_hash: None,
+        });
+        self.accounts.len() - 1
+    }
+
+    fn confirm(&mut self, index: usize) {
+        let user = &mut self.accounts[index].user;
+        if !user.email.is_empty() && user.email_confirmed_at.is_none() {
+            user.email_confirmed_at = Some(now());
+        }
+        if !user.phone.is_empty() {
+            user.phone_confirmed = Some(true);
+        }
+    }
+
+    fn sign_in(&mut self, index: usize) -> Reply {
+        let access_token = self.token("access");
+        let refresh_token = self.token("refresh");
+
+        let user = &mut self.accounts[index].user;
+        user.last_sign_in_at = Some(now());
+        let user = user.clone();
+
+        self.sessions.push(FakeSession {
+            access_token: access_token.clone(),
+            refresh_token: refresh_token.clone(),
+            user_id: user.id.clone(),
+        });
+
+        let session = Session {
+            access_token,
+            token_type: "bearer".to_owned(),
+            expires_in: 3600,
+            refresh_token,
+            user,
+        };
+        Reply::json(200, json!(session))
+    }
+
+    fn authenticated(&self, call: &Call) -> Option<usize> {
+        let bearer = call.bearer?;
+        let session = self
+            .sessions
+            .iter()
+            .find(|session| session.access_token == bearer)?;
+        self.find_id(&session.user_id)
+    }
+
+    pub fn route(&mut self, call: &Call) -> Reply {
+        let segments: Vec<&str> = call.path.trim_matches('/').split('/').collect();
+
+        match (call.method, segments.as_slice()) {
+            ("GET", ["health"]) => Reply::json(
+                200,
+                json!({
+                    "version": "test-util",
+                    "name": "GoTrue",
+                    "description": "An in-memory fake of GoTrue",
+                }),
+            ),
+            ("GET", ["settings"]) => Reply::json(200, json!(self.settings)),
+            ("POST", ["signup"]) => self.signup(call),
+            ("POST", ["token"]) => match call.query.get("grant_type").map(String::as_str) {
+                Some("password") => self.password_grant(call),
+                Some("refresh_token") => self.refresh_token_grant(call),
+                _ => Reply::json(
+                    400,
+                    json!({
+                        "error": "unsupported_grant_type",
+                        "error_description": "unsupported_grant_type",
+                    }),
+                ),
+            },
+            ("POST", ["otp"]) | ("POST", ["magiclink"]) => self.otp(call),
+            ("POST", ["recover"]) | ("POST", ["resend"]) => self.resend(call),
+            ("POST", ["verify"]) => self.verify(call),
+            ("GET", ["user"]) => match self.authenticated(call) {
+                Some(index) => Reply::json(200, json!(self.accounts[index].user)),
+                None => Reply::error(401, "invalid JWT"),
+            },
+            ("PUT", ["user"]) => self.update_user(call),
+            ("POST", ["logout"]) => self.logout(call),
+            (_, ["admin", ..]) if call.bearer != Some(self.service_role_key.as_str()) => {
+                Reply::error(401, "User not allowed")
+            }
+            ("GET", ["admin", "users"]) => self.list_users(call),
+            ("POST", ["admin", "users"]) => self.admin_create_user(call),
+            ("GET", ["admin", "users", id]) => match self.find_id(id) {
+                Some(index) => Reply::json(200, json!(self.accounts[index].user)),
+                None => Reply::error(404, "User not found"),
+            },
+            ("PUT", ["admin", "users", id]) => match self.find_id(id) {
+                Some(index) => {
+                    self.admin_update(index, &call.body);
+                    Reply::json(200, json!(self.accounts[index].user))
+                }
+                None => Reply::error(404, "User not found"),
+            },
+            ("DELETE", ["admin", "users", id]) => match self.find_id(id) {
+                Some(index) => {
+                    let account = self.accounts.remove(index);
+                    self.sessions
+                        .retain(|session| session.user_id != account.user.id);
+                    Reply::json(200, json!({}))
+                }
+                None => Reply::error(404, "User not found"),
+            },
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+
+    fn signup(&mut self, call: &Call) -> Reply {
+        if self.settings.disable_signup {
+            return Reply::error(422, "Signups not allowed for this instance");
+        }
+
+        let (email, phone) = (call.str("email"), call.str("phone"));
+        if email.is_none() && phone.is_none() {
+            return Reply::error(422, "Signup requires a valid email or phone");
+        }
+        if self.find(email, phone).is_some() {
+            return Reply::error(422, "User already registered");
+        }
+
+        let index = self.create(email, phone);
+        self.accounts[index].password = call.str("password").map(str::to_owned);
+        if let Some(data) = call.body.get("data") {
+            merge(&mut self.accounts[index].user.user_metadata, data);
+        }
+
+        let autoconfirm = match email {
+            Some(_) => self.settings.autoconfirms_email(),
+            None => self.settings.phone_autoconfirm,
+        };
+        if autoconfirm {
+            self.confirm(index);
+            self.sign_in(index)
+        } else {
+            self.send_code(email.or(phone).unwrap_or_default());
+            Reply::json(200, json!(self.accounts[index].user))
+        }
+    }
+
+    fn password_grant(&mut self, call: &Call) -> Reply {
+        let index = match self.find(call.str("email"), call.str("phone")) {
+            Some(index) => index,
+            None => return Reply::grant_error("Invalid login credentials"),
+        };
+
+        let account = &self.accounts[index];
+        if account.password.is_none() || account.password.as_deref() != call.str("password") {
+            return Reply::grant_error("Invalid login credentials");
+        }
+        if call.str("email").is_some() && account.user.email_confirmed_at.is_none() {
+            return Reply::grant_error("Email not confirmed");
+        }
+
+        self.sign_in(index)
+    }
+
+    fn refresh_token_grant(&mut self, call: &Call) -> Reply {
+        let refresh_token = call.str("refresh_token").unwrap_or_default();
+        let position = self
+            .sessions
+            .iter()
+            .position(|session| session.refresh_token == refresh_token);
+
+        let session = match position {
+            Some(position) => self.sessions.remove(position),
+            None => return Reply::grant_error("Invalid Refresh Token: Refresh Token Not Found"),
+        };
+
+        match self.find_id(&session.user_id) {
+            Some(index) => self.sign_in(index),
+            None => Reply::grant_error("Invalid Refresh Token: Refresh Token Not Found"),
+        }
+    }
+
+    fn otp(&mut self, call: &Call) -> Reply {
+        let (email, phone) = (call.str("email"), call.str("phone"));
+        let address = match email.or(phone) {
+            Some(address) => address.to_owned(),
+            None => return Reply::error(422, "An email address or phone number is required"),
+        };
+
+        if self.find(email, phone).is_none() {
+            let create_user = call
+                .body
+                .get("create_user")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            if !create_user || self.settings.disable_signup {
+                return Reply::error(422, "Signups not allowed for otp");
+            }
+
+            let index = self.create(email, phone);
+            if let Some(data) = call.body.get("data") {
+                merge(&mut self.accounts[index].user.user_metadata, data);
+            }
+        }
+
+        self.send_code(&address);
+        Reply::json(200, json!({}))
+    }
+
+    fn resend(&mut self, call: &Call) -> Reply {
+        let address = match call.str("email").or(call.str("phone")) {
+            Some(address) => address.to_owned(),
+            None => return Reply::error(422, "An email address or phone number is required"),
+        };
+
+        // Changes are looked up by the current address and sent to the new one.
+        let pending = match call.str("type") {
+            Some("email_change") => self
+                .find(call.str("email"), None)
+                .map(|index| self.accounts[index].user.new_email.clone()),
+            Some("phone_change") => self
+                .find(None, call.str("phone"))
+                .map(|index| self.accounts[index].user.new_phone.clone()),
+            _ => {
+                self.send_code(&address);
+                return Reply::json(200, json!({}));
+            }
+        };
+
+        match pending.flatten() {
+            Some(new_address) => {
+                self.send_code(&new_address);
+                Reply::json(200, json!({}))
+            }
+            None => Reply::error(422, "No pending change for this user"),
+        }
+    }
+
+    fn verify(&mut self, call: &Call) -> Reply {
+        let address = call.str("email").or(call.str("phone")).unwrap_or_default();
+        let token = call.str("token").unwrap_or_default();
+
+        if self.codes.get(address).map(String::as_str) != Some(token) || token.is_empty() {
+            return Reply::error(403, "Token has expired or is invalid");
+        }
+        self.codes.remove(address);
+
+        let index = match call.str("type") {
+            Some("email_change") => self
+                .accounts
+                .iter()
+                .position(|account| account.user.new_email.as_deref() == Some(address)),
+            Some("phone_change") => self
+                .accounts
+                .iter()
+                .position(|account| account.user.new_phone.as_deref() == Some(address)),
+            _ => self.find(call.str("email"), call.str("phone")),
+        };
+        let index = match index {
+            Some(index) => index,
+            None => return Reply::error(403, "Token has expired or is invalid"),
+        };
+
+        let user = &mut self.accounts[index].user;
+        match call.str("type") {
+            Some("email_change") => {
+                user.email = user.new_email.take().unwrap_or_default();
+                user.email_change_sent_at = None;
+            }
+            Some("phone_change") => {
+                user.phone = user.new_phone.take().unwrap_or_default();
+                user.phone_change_sent_at = None;
+            }
+            _ => {}
+        }
+        user.updated_at = now();
+
+        self.confirm(index);
+        self.sign_in(index)
+    }
+
+    fn update_user(&mut self, call: &Call) -> Reply {
+        let index = match self.authenticated(call) {
+            Some(index) => index,
+            None => return Reply::error(401, "invalid JWT"),
+        };
+
+        if let Some(password) = call.str("password") {
+            self.accounts[index].password = Some(password.to_owned());
+        }
+        if let Some(data) = call.body.get("data") {
+            merge(&mut self.accounts[index].user.user_metadata, data);
+        }
+        if let Some(email) = call
+            .str("email")
+            .filter(|email| *email != self.accounts[index].user.email)
+        {
+            let user = &mut self.accounts[index].user;
+            user.new_email = Some(email.to_owned());
+            user.email_change_sent_at = Some(now());
+            self.send_code(email);
+        }
+        if let Some(phone) = call
+            .str("phone")
+            .filter(|phone| *phone != self.accounts[index].user.phone)
+        {
+            let user = &mut self.accounts[index].user;
+            user.new_phone = Some(phone.to_owned());
+            user.phone_change_sent_at = Some(now());
+            self.send_code(phone);
+        }
+
+        let user = &mut self.accounts[index].user;
+        user.updated_at = now();
+        Reply::json(200, json!(user))
+    }
+
+    fn logout(&mut self, call: &Call) -> Reply {
+        let index = match self.authenticated(call) {
+            Some(index) => index,
+            None => return Reply::error(401, "invalid JWT"),
+        };
+
+        let user_id = self.accounts[index].user.id.clone();
+        let current = call.bearer.unwrap_or_default();
+        match call.query.get("scope").map(String::as_str) {
+            Some("local") => self
+                .sessions
+                .retain(|session| session.access_token != current),
+            Some("others") => self
+                .sessions
+                .retain(|session| session.user_id != user_id || session.access_token == current),
+            _ => self.sessions.retain(|session| session.user_id != user_id),
+        }
+
+        Reply::no_content()
+    }
+
+    fn list_users(&mut self, call: &Call) -> Reply {
+        let page: usize = call
+            .query
+            .get("page")
+            .and_then(|page| page.parse().ok())
+            .unwrap_or(1)
+            .max(1);
+        let per_page: usize = call
+            .query
+            .get("per_page")
+            .and_then(|per_page| per_page.parse().ok())
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .max(1);
+        let filter = call.query.get("filter").map(|filter| filter.to_lowercase());
+
+        let mut users: Vec<&User> = self
+            .accounts
+            .iter()
+            .map(|account| &account.user)
+            .filter(|user| {
+                filter
+                    .as_ref()
+                    .map_or(true, |filter| user.email.to_lowercase().contains(filter))
+            })
+            .collect();
+        if call
+            .query
+            .get("sort")
+            .is_some_and(|sort| sort.ends_with("desc"))
+        {
+            users.reverse();
+        }
+
+        let total = users.len();
+        let page_users: Vec<&User> = users
+            .into_iter()
+            .skip((page - 1) * per_page)
+            .take(per_page)
+            .collect();
+
+        let mut reply = Reply::json(200, json!({ "aud": "authenticated", "users": page_users }));
+        reply.headers.push(("x-total-count", total.to_string()));
+        // GoTrue links to the other pages relative to its own URL.
+        if page * per_page < total {
+            reply.headers.push((
+                "link",
+                format!(
+                    "</admin/users?page={}&per_page={per_page}>; rel=\"next\"",
+                    page + 1
+                ),
+            ));
+        }
+        reply
+    }
+
+    fn admin_create_user(&mut self, call: &Call) -> Reply {
+        let (email, phone) = (call.str("email"), call.str("phone"));
+        if email.is_none() && phone.is_none() {
+            return Reply::error(422, "Unable to validate email address: invalid format");
+        }
+        if self.find(email, phone).is_some() {
+            return Reply::error(
+                422,
+                "A user with this email address has already been registered",
+            );
+        }
+
+        let index = self.create(email, phone);
+        if let Some(id) = call.str("id") {
+            self.accounts[index].user.id = id.to_owned();
+        }
+        self.admin_update(index, &call.body);
+
+        Reply::json(200, json!(self.accounts[index].user))
+    }
+
+    fn admin_update(&mut self, index: usize, body: &Value) {
+        let confirmed_at = now();
+        let account = &mut self.accounts[index];
+        let str = |key: &str| body.get(key).and_then(Value::as_str);
+
+        if let Some(email) = str("email") {
+            account.user.email = email.to_owned();
+        }
+        if let Some(phone) = str("phone") {
+            account.user.phone = phone.to_owned();
+        }
+        if let Some(password) = str("password") {
+            account.password = Some(password.to_owned());
+            account.password_hash = None;
+        }
+        if let Some(password_hash) = str("password_hash") {
+            account.password = None;
+            account.password_hash = Some(password_hash.to_owned());
+        }
+        if let Some(role) = str("role") {
+            account.user.role = role.to_owned();
+        }
+        if body.get("email_confirm").and_then(Value::as_bool) == Some(true) {
+            account.user.email_confirmed_at = Some(confirmed_at.clone());
+        }
+        if body.get("phone_confirm").and_then(Value::as_bool) == Some(true) {
+            account.user.phone_confirmed = Some(true);
+        }
+        if let Some(data) = body.get("user_metadata") {
+            merge(&mut account.user.user_metadata, data);
+        }
+        if let Some(data) = body.get("app_metadata") {
+            merge(&mut account.user.app_metadata, data);
+        }
+        account.user.updated_at = confirmed_at;
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Represents a User.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
@@ -19,14 +20,32 @@ pub struct User {
     pub role: String,
     /// The date the User's email was confirmed, if confirmed.
     pub email_confirmed_at: Option<String>,
+    /// The email the User is changing to, until the change is confirmed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_email: Option<String>,
     /// The User's phone number.
     pub phone: String,
     /// The date the User's phone was confirmed, if confirmed.
     pub phone_confirmed: Option<bool>,
+    /// The phone number the User is changing to, until the change is confirmed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_phone: Option<String>,
+    /// The date the last email change confirmation was sent, if a change is pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_change_sent_at: Option<String>,
+    /// The date the last phone change OTP was sent, if a change is pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_change_sent_at: Option<String>,
     /// The User's last login, if the user has logged in.
     pub last_sign_in_at: Option<String>,
     /// The date the User was created.
     pub created_at: String,
     /// The date the User was last updated.
     pub updated_at: String,
+    /// Metadata the User can update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_metadata: Option<Value>,
+    /// Metadata only admins can update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_metadata: Option<Value>,
 }
@@ -4,12 +4,76 @@ use serde_json::Value;
 ///
 /// Represents user attributes used in updating a user.
 ///
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Only the attributes that are set are sent, so changing the password does
+/// not touch the email and vice versa.
+///
+/// # Example
+///
+/// ```
+/// use go_true_redux::UserAttributes;
+/// use serde_json::json;
+///
+/// let attributes = UserAttributes::default()
+///     .with_password("Abcd12345!")
+///     .with_data(json!({ "plan": "pro" }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct UserAttributes {
-    /// The email of a user.
-    pub email: String,
-    /// The password of a user.
-    pub password: String,
-    /// Additional user data.
-    pub data: Value,
+    /// The new email of the user. GoTrue sends a confirmation before applying it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// The new phone number of the user. GoTrue sends an OTP before applying it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// The new password of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// The nonce sent by [`crate::Client::reauthenticate`], required to change
+    /// the password when GoTrue enforces reauthentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Additional user data, stored in the user metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// Where to send the user after they confirm a new email. Defaults to the site URL.
+    #[serde(skip)]
+    pub email_redirect_to: Option<String>,
+}
+
+impl UserAttributes {
+    /// Changes the email of the user.
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Changes the phone number of the user.
+    pub fn with_phone(mut self, phone: impl Into<String>) -> Self {
+        self.phone = Some(phone.into());
+        self
+    }
+
+    /// Changes the password of the user.
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Sets the reauthentication nonce.
+    pub fn with_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets additional user data.
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets where to send the user after they confirm a new email.
+    pub fn with_email_redirect_to(mut self, email_redirect_to: impl Into<String>) -> Self {
+        self.email_redirect_to = Some(email_redirect_to.into());
+        self
+    }
 }
@@ -1,7 +1,15 @@
 use crate::user::User;
 use serde::{Deserialize, Serialize};
 
+/// Represents a page of users returned by [`crate::Client::list_users`].
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserList {
+    /// The users on this page.
     pub users: Vec<User>,
+    /// The total number of users matching the query, if GoTrue reported it.
+    #[serde(skip)]
+    pub total: Option<u64>,
+    /// The next page, if there is one.
+    #[serde(skip)]
+    pub next_page: Option<u32>,
 }
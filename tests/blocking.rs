@@ -0,0 +1,82 @@
+use go_true_redux::{
+    blocking::Client, test_util::MockGoTrue, AdminUserAttributes, EmailOrPhone, Error,
+    ExportFormat, ListUsersQuery,
+};
+use tokio::runtime::Runtime;
+
+/// Starts the mock server on its own runtime, since the blocking client must
+/// not run on one.
+fn start() -> (Runtime, MockGoTrue) {
+    let runtime = Runtime::new().unwrap();
+    let server = runtime.block_on(MockGoTrue::start()).unwrap();
+    (runtime, server)
+}
+
+#[test]
+fn it_signs_up_and_in() -> anyhow::Result<()> {
+    let (_runtime, server) = start();
+    let client = Client::new(server.url());
+    let email = EmailOrPhone::Email("email@example.com".to_string());
+
+    client.sign_up(email.clone(), "Abcd1234!")?;
+    assert!(matches!(
+        client.sign_in(email.clone(), "wrong"),
+        Err(Error::Api { status: 400, .. })
+    ));
+
+    let session = client.sign_in(email, "Abcd1234!")?;
+    let user = client.get_user(&session.access_token)?;
+    assert_eq!(user.email, "email@example.com");
+
+    Ok(())
+}
+
+#[test]
+fn it_iterates_over_every_user() -> anyhow::Result<()> {
+    let (_runtime, server) = start();
+    let client = Client::from(server.admin_client());
+
+    for i in 0..3 {
+        client.admin_create_user(&AdminUserAttributes {
+            email: Some(format!("user{i}@example.com")),
+            ..Default::default()
+        })?;
+    }
+
+    let emails = client
+        .list_all_users(ListUsersQuery::default().with_per_page(2))
+        .map(|user| user.map(|user| user.email))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(
+        emails,
+        [
+            "user0@example.com",
+            "user1@example.com",
+            "user2@example.com"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_exports_to_a_std_writer() -> anyhow::Result<()> {
+    let (_runtime, server) = start();
+    let client = Client::from(server.admin_client());
+
+    client.admin_create_user(&AdminUserAttributes {
+        email: Some("user@example.com".to_string()),
+        ..Default::default()
+    })?;
+
+    let mut out = Vec::new();
+    let count = client.export_users(ListUsersQuery::default(), &ExportFormat::csv(), &mut out)?;
+
+    assert_eq!(count, 1);
+    let csv = String::from_utf8(out)?;
+    assert!(csv.starts_with("id,email,"));
+    assert!(csv.lines().nth(1).unwrap().contains("user@example.com"));
+
+    Ok(())
+}
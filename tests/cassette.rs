@@ -0,0 +1,132 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{response, serve};
+use go_true_redux::{AdminUserAttributes, Cassette, CassetteMode, Client, Error};
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "go_true_redux-{}-{}.json",
+        name,
+        std::process::id()
+    ))
+}
+
+#[tokio::test]
+async fn it_replays_recorded_exchanges_without_the_network() {
+    let server = serve(vec![response(
+        "200 OK",
+        "",
+        r#"{"external":{"email":true,"phone":false},"disable_signup":false,"autoconfirm":true}"#,
+    )])
+    .await;
+    let path = cassette_path("replay");
+
+    let cassette = Cassette::record(&path);
+    assert_eq!(cassette.mode(), CassetteMode::Record);
+    let recorded = Client::new(&server.url)
+        .with_cassette(cassette)
+        .get_settings()
+        .await
+        .unwrap();
+    assert_eq!(server.hits(), 1);
+
+    let cassette = Cassette::replay(&path).unwrap();
+    let replayed = Client::new(&server.url)
+        .with_cassette(cassette)
+        .get_settings()
+        .await
+        .unwrap();
+
+    assert_eq!(server.hits(), 1);
+    assert_eq!(replayed.autoconfirm, recorded.autoconfirm);
+    assert_eq!(replayed.external, recorded.external);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn it_answers_unrecorded_requests_with_an_error() {
+    let path = cassette_path("miss");
+    Cassette::record(&path).save().unwrap();
+
+    let client = Client::new("http://localhost:1").with_cassette(Cassette::replay(&path).unwrap());
+    let result = client.get_settings().await;
+
+    assert!(matches!(result, Err(Error::InternalError)));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn it_replays_by_request_body() {
+    let user = |email: &str| {
+        format!(
+            r#"{{"id":"{email}","email":"{email}","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}}"#
+        )
+    };
+    let server = serve(vec![
+        response("200 OK", "", &user("a@example.com")),
+        response("200 OK", "", &user("b@example.com")),
+    ])
+    .await;
+    let path = cassette_path("body");
+    let attributes = |email: &str| AdminUserAttributes {
+        email: Some(email.to_string()),
+        ..Default::default()
+    };
+
+    let client = Client::new(&server.url).with_cassette(Cassette::record(&path));
+    client
+        .admin_create_user(&attributes("a@example.com"))
+        .await
+        .unwrap();
+    client
+        .admin_create_user(&attributes("b@example.com"))
+        .await
+        .unwrap();
+    drop(client);
+
+    // Replayed in the other order, each request still gets its own response.
+    let client = Client::new(&server.url).with_cassette(Cassette::replay(&path).unwrap());
+    let b = client
+        .admin_create_user(&attributes("b@example.com"))
+        .await
+        .unwrap();
+    let a = client
+        .admin_create_user(&attributes("a@example.com"))
+        .await
+        .unwrap();
+
+    assert_eq!(server.hits(), 2);
+    assert_eq!(a.email, "a@example.com");
+    assert_eq!(b.email, "b@example.com");
+    assert!(matches!(
+        client.admin_create_user(&attributes("c@example.com")).await,
+        Err(Error::InternalError)
+    ));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn it_keeps_values_between_recording_and_replaying() {
+    let path = cassette_path("values");
+
+    let cassette = Cassette::record(&path);
+    let recorded = cassette.value("email", || "first@example.com".to_string());
+    assert_eq!(
+        cassette.value("email", || "second@example.com".to_string()),
+        recorded
+    );
+    drop(cassette);
+
+    let cassette = Cassette::replay(&path).unwrap();
+    assert_eq!(
+        cassette.value("email", || "other@example.com".to_string()),
+        "first@example.com"
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,131 @@
+use std::process::{Command, Output};
+
+use go_true_redux::{test_util::MockGoTrue, AdminUserAttributes};
+
+fn gotrue(server: &MockGoTrue, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gotrue"))
+        .env_remove("GOTRUE_URL")
+        .env("GOTRUE_SERVICE_KEY", server.service_role_key())
+        .arg("--url")
+        .arg(server.url())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_creates_and_lists_users() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+
+    let output = gotrue(
+        &server,
+        &["create-user", "--email", "email@example.com", "--confirm"],
+    );
+    assert!(output.status.success());
+
+    let output = gotrue(&server, &["--output", "json", "list-users"]);
+    assert!(output.status.success());
+    let users: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["email"], "email@example.com");
+    assert!(users[0]["email_confirmed_at"].is_string());
+
+    let output = gotrue(&server, &["list-users"]);
+    let table = String::from_utf8(output.stdout)?;
+    assert!(table.starts_with("ID"));
+    assert!(table.contains("email@example.com"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_confirms_emails() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let user = server
+        .admin_client()
+        .admin_create_user(&AdminUserAttributes {
+            email: Some("email@example.com".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert!(user.email_confirmed_at.is_none());
+
+    let output = gotrue(&server, &["confirm-email", &user.id]);
+    assert!(output.status.success());
+
+    assert!(server.users()[0].email_confirmed_at.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_fails_without_the_service_key() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_gotrue"))
+        .env_remove("GOTRUE_SERVICE_KEY")
+        .args(["--url", server.url(), "list-users"])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.starts_with("error: "));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_shows_the_health_of_gotrue() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+
+    let output = gotrue(&server, &["--output", "json", "health", "--wait", "5"]);
+    assert!(output.status.success());
+
+    let health: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(health["version"], "test-util");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_refuses_a_page_with_all() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+
+    let output = gotrue(&server, &["list-users", "--all", "--page", "2"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("--page"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_exports_users_to_a_file() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    server
+        .admin_client()
+        .admin_create_user(&AdminUserAttributes {
+            email: Some("email@example.com".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let path = std::env::temp_dir().join(format!("gotrue-export-{}.csv", std::process::id()));
+
+    let output = gotrue(
+        &server,
+        &[
+            "export-users",
+            "--format",
+            "csv",
+            "--file",
+            path.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+
+    let csv = std::fs::read_to_string(&path)?;
+    assert!(csv.starts_with("id,email,"));
+    assert!(csv.contains("email@example.com"));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use go_true_redux::{Client, EmailOrPhone, User, UserAttributes};
+use go_true_redux::{Cassette, Client, EmailOrPhone, User, UserAttributes};
 use rand::{distributions::Alphanumeric, Rng};
 use serde_json::json;
 use std::error::Error;
@@ -6,6 +6,7 @@ use std::error::Error;
 use hmac::{Hmac, Mac};
 use jwt::SignWithKey;
 use sha2::Sha256;
+use std::cell::Cell;
 use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
@@ -20,40 +21,86 @@ struct AdminUserAttributes {
     pub phone_confirmed: Option<bool>,
 }
 
-fn get_api_client() -> Client {
-    Client::new("http://localhost:9998")
+/// The client and emails of one test, recording to or replaying from its own
+/// cassette when `GOTRUE_CASSETTE` is `record` or `replay`.
+struct Recording {
+    cassette: Option<Cassette>,
+    emails: Cell<usize>,
 }
 
-fn get_service_api_client() -> Client {
-    let key: Hmac<Sha256> = Hmac::new_from_slice(b"37c304f8-51aa-419a-a1af-06154e63707a").unwrap();
-    let mut claims = BTreeMap::new();
-    claims.insert("sub", "1234567890");
-    claims.insert("role", "supabase_admin");
+impl Recording {
+    fn new(name: &'static str) -> Self {
+        let path = format!("tests/cassettes/client/{name}.json");
+        let cassette = match std::env::var("GOTRUE_CASSETTE").as_deref() {
+            Ok("record") => Some(Cassette::record(path)),
+            Ok("replay") => Some(Cassette::replay(&path).unwrap_or_else(|e| {
+                panic!("cannot replay {path}, record it first with GOTRUE_CASSETTE=record: {e}")
+            })),
+            _ => None,
+        };
+
+        Recording {
+            cassette,
+            emails: Cell::new(0),
+        }
+    }
 
-    let token_str = claims.sign_with_key(&key).unwrap();
-    let api: Client = Client::new("http://localhost:9998")
-        .with_header("Authorization", format!("Bearer {token_str}"));
+    fn with_cassette(&self, client: Client) -> Client {
+        match &self.cassette {
+            Some(cassette) => client.with_cassette(cassette.clone()),
+            None => client,
+        }
+    }
 
-    api
-}
+    fn api_client(&self) -> Client {
+        self.with_cassette(Client::new("http://localhost:9998"))
+    }
+
+    fn service_api_client(&self) -> Client {
+        let key: Hmac<Sha256> =
+            Hmac::new_from_slice(b"37c304f8-51aa-419a-a1af-06154e63707a").unwrap();
+        let mut claims = BTreeMap::new();
+        claims.insert("sub", "1234567890");
+        claims.insert("role", "supabase_admin");
+
+        let token_str = claims.sign_with_key(&key).unwrap();
+        let api: Client = Client::new("http://localhost:9998")
+            .with_header("Authorization", format!("Bearer {token_str}"));
 
-fn get_random_email() -> String {
-    let random_string: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(7)
-        .map(|c| c.to_ascii_lowercase())
-        .map(char::from)
-        .collect();
+        self.with_cassette(api)
+    }
 
-    format!("{random_string}@example.com")
+    fn email(&self) -> String {
+        let random_email = || {
+            let random_string: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(7)
+                .map(|c| c.to_ascii_lowercase())
+                .map(char::from)
+                .collect();
+
+            format!("{random_string}@example.com")
+        };
+
+        // Recorded requests and responses contain the email, so a fresh one is
+        // kept in the cassette when recording and read back when replaying.
+        match &self.cassette {
+            Some(cassette) => {
+                let n = self.emails.replace(self.emails.get() + 1);
+                cassette.value(&format!("email-{n}"), random_email)
+            }
+            None => random_email(),
+        }
+    }
 }
 
 #[tokio::test]
 async fn it_signs_up_with_email() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_signs_up_with_email");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let res = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -65,10 +112,11 @@ async fn it_signs_up_with_email() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_signs_in_with_email() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_signs_in_with_email");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -82,10 +130,11 @@ async fn it_signs_in_with_email() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_send_magic_link_with_valid_email() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_send_magic_link_with_valid_email");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -100,8 +149,9 @@ async fn it_send_magic_link_with_valid_email() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_does_not_send_magic_link_with_invalid_email() -> Result<(), Box<dyn Error>> {
+    let tape = Recording::new("it_does_not_send_magic_link_with_invalid_email");
     let email = String::from("i-do-not-exist");
-    let api = get_api_client();
+    let api = tape.api_client();
     let response = api.send_otp(EmailOrPhone::Email(email), None).await;
 
     match response {
@@ -112,10 +162,11 @@ async fn it_does_not_send_magic_link_with_invalid_email() -> Result<(), Box<dyn
 
 #[tokio::test]
 async fn it_should_log_out() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_log_out");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -125,19 +176,18 @@ async fn it_should_log_out() -> Result<(), Box<dyn Error>> {
 
     assert_eq!(res.user.email, email);
 
-    let success = api.sign_out(&res.access_token).await?;
-
-    assert!(success);
+    api.sign_out(&res.access_token).await?;
 
     Ok(())
 }
 
 #[tokio::test]
 async fn it_should_return_error_if_token_is_invalid() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_return_error_if_token_is_invalid");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -157,10 +207,11 @@ async fn it_should_return_error_if_token_is_invalid() -> Result<(), Box<dyn Erro
 
 #[tokio::test]
 async fn it_should_send_password_recovery_email() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_send_password_recovery_email");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -173,7 +224,8 @@ async fn it_should_send_password_recovery_email() -> Result<(), Box<dyn Error>>
 
 #[test]
 fn it_should_return_url_for_provider() {
-    let api = get_api_client();
+    let tape = Recording::new("it_should_return_url_for_provider");
+    let api = tape.api_client();
     let url = api.get_url_for_provider("Github");
 
     assert!(url.ends_with("/authorize?provider=Github"));
@@ -181,10 +233,11 @@ fn it_should_return_url_for_provider() {
 
 #[tokio::test]
 async fn it_should_refresh_token() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_refresh_token");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -201,10 +254,11 @@ async fn it_should_refresh_token() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_return_user() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_return_user");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
@@ -221,33 +275,34 @@ async fn it_should_return_user() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_update_user() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_update_user");
+    let email = tape.email();
     let password = String::from("Abcd1234!");
 
-    let api = get_api_client();
+    let api = tape.api_client();
     let _throw_away_signup_result = api
         .sign_up(EmailOrPhone::Email(email.clone()), &password)
         .await?;
     let session = api.sign_in(EmailOrPhone::Email(email), &password).await?;
 
-    let new_email = get_random_email();
-    let attributes = UserAttributes {
-        email: new_email.clone(),
-        password: "Abcd12345!".to_string(),
-        data: json!({ "test": "test" }),
-    };
+    let new_email = tape.email();
+    let attributes = UserAttributes::default()
+        .with_email(new_email.clone())
+        .with_password("Abcd12345!")
+        .with_data(json!({ "test": "test" }));
 
     let update = api.update_user(attributes, &session.access_token).await?;
 
-    assert_eq!(update.new_email, new_email);
+    assert_eq!(update.new_email, Some(new_email));
 
     Ok(())
 }
 
 #[tokio::test]
 async fn it_should_invite_user_by_email() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
-    let api = get_service_api_client();
+    let tape = Recording::new("it_should_invite_user_by_email");
+    let email = tape.email();
+    let api = tape.service_api_client();
     let user = api.invite_user_by_email(&email).await?;
 
     assert_eq!(user.email, email);
@@ -257,14 +312,15 @@ async fn it_should_invite_user_by_email() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_list_users() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_list_users");
+    let email = tape.email();
     let password = "Abcd1234!";
-    let client_api = get_api_client();
+    let client_api = tape.api_client();
     let _throw_away_signup_result = client_api
         .sign_up(EmailOrPhone::Email(email), password)
         .await?;
 
-    let api = get_service_api_client();
+    let api = tape.service_api_client();
     let users = api.list_users(None).await?;
 
     assert!(!users.users.is_empty());
@@ -274,14 +330,15 @@ async fn it_should_list_users() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_get_user_by_id() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
+    let tape = Recording::new("it_should_get_user_by_id");
+    let email = tape.email();
     let password = "Abcd1234!";
-    let client_api = get_api_client();
+    let client_api = tape.api_client();
     let session = client_api
         .sign_up(EmailOrPhone::Email(email.clone()), password)
         .await?;
 
-    let api = get_service_api_client();
+    let api = tape.service_api_client();
     let user = api.get_user_by_id(&session.user.id).await?;
 
     assert_eq!(user.email, email);
@@ -291,8 +348,9 @@ async fn it_should_get_user_by_id() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_update_user_by_id() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
-    let api = get_service_api_client();
+    let tape = Recording::new("it_should_update_user_by_id");
+    let email = tape.email();
+    let api = tape.service_api_client();
     let user = User {
         email: email.clone(),
         password: Some("Abcd1234!".to_owned()),
@@ -305,7 +363,7 @@ async fn it_should_update_user_by_id() -> Result<(), Box<dyn Error>> {
     let create_response = api.create_user(user).await?;
     assert_eq!(create_response.email, email);
 
-    let new_email = get_random_email();
+    let new_email = tape.email();
 
     let user = AdminUserAttributes {
         email: new_email.clone(),
@@ -326,8 +384,9 @@ async fn it_should_update_user_by_id() -> Result<(), Box<dyn Error>> {
 
 #[tokio::test]
 async fn it_should_delete_user() -> Result<(), Box<dyn Error>> {
-    let email = get_random_email();
-    let api = get_service_api_client();
+    let tape = Recording::new("it_should_delete_user");
+    let email = tape.email();
+    let api = tape.service_api_client();
     let user = User {
         email: email.clone(),
         password: Some("Abcd1234!".to_owned()),
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, ExportFormat, ListUsersQuery};
+
+const PAGE: &str = r#"{"aud":"authenticated","users":[
+    {"id":"1","email":"a@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z","user_metadata":{"plan":"pro","name":"Doe, Jane"}},
+    {"id":"2","email":"b@example.com","aud":"authenticated","role":"authenticated","phone":"15555550100","created_at":"2023-01-02T00:00:00Z","updated_at":"2023-01-02T00:00:00Z"}
+]}"#;
+
+#[tokio::test]
+async fn it_exports_json_lines() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "x-total-count: 2\r\n", PAGE)]).await;
+
+    let client = Client::new(&server.url);
+    let mut out = Vec::new();
+    let count = client
+        .export_users(
+            ListUsersQuery::default(),
+            &ExportFormat::JsonLines,
+            &mut out,
+        )
+        .await?;
+
+    assert_eq!(count, 2);
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["user_metadata"]["plan"], "pro");
+    assert_eq!(lines[1]["phone"], "15555550100");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_exports_csv_with_flattened_metadata() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "x-total-count: 2\r\n", PAGE)]).await;
+
+    let client = Client::new(&server.url);
+    let format = ExportFormat::Csv {
+        columns: vec![
+            "id".into(),
+            "email".into(),
+            "user_metadata.plan".into(),
+            "user_metadata.name".into(),
+        ],
+    };
+    let mut out = Vec::new();
+    client
+        .export_users(ListUsersQuery::default(), &format, &mut out)
+        .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "id,email,user_metadata.plan,user_metadata.name\n\
+         1,a@example.com,pro,\"Doe, Jane\"\n\
+         2,b@example.com,,\n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_exports_default_csv_columns() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "x-total-count: 2\r\n", PAGE)]).await;
+
+    let client = Client::new(&server.url);
+    let mut out = Vec::new();
+    client
+        .export_users(ListUsersQuery::default(), &ExportFormat::csv(), &mut out)
+        .await?;
+
+    let out = String::from_utf8(out)?;
+    let mut lines = out.lines();
+    assert_eq!(
+        lines.next(),
+        Some("id,email,phone,role,aud,email_confirmed_at,last_sign_in_at,created_at,updated_at")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("1,a@example.com,,authenticated,authenticated,,,2023-01-01T00:00:00Z,2023-01-01T00:00:00Z")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error, GenerateLinkOptions, GenerateLinkParams};
+
+const LINK: &str = r#"{"action_link":"http://localhost:9998/verify?token=abc&type=recovery","email_otp":"123456","hashed_token":"abc","verification_type":"recovery","redirect_to":"https://example.com","id":"1","aud":"authenticated","role":"authenticated","email":"email@example.com","phone":"","created_at":"","updated_at":""}"#;
+
+#[tokio::test]
+async fn it_generates_a_link() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", LINK)]).await;
+
+    let client = Client::new(&server.url);
+    let params = GenerateLinkParams::EmailChangeNew {
+        email: "email@example.com".to_string(),
+        new_email: "new@example.com".to_string(),
+    };
+    let options = GenerateLinkOptions {
+        redirect_to: Some("https://example.com".to_string()),
+        ..Default::default()
+    };
+    let link = client
+        .generate_email_action_link(&params, Some(options))
+        .await?;
+
+    assert_eq!(link.email_otp.as_deref(), Some("123456"));
+    assert_eq!(link.user.email, "email@example.com");
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /admin/generate_link "));
+    assert!(request.contains(r#""type":"email_change_new""#));
+    assert!(request.contains(r#""new_email":"new@example.com""#));
+    assert!(request.contains(r#""redirect_to":"https://example.com""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_for_unknown_users() {
+    let server = serve(vec![response(
+        "404 Not Found",
+        "",
+        r#"{"code":404,"msg":"User not found"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let params = GenerateLinkParams::Recovery {
+        email: "email@example.com".to_string(),
+    };
+    let result = client.generate_email_action_link(&params, None).await;
+
+    assert!(matches!(result, Err(Error::Api { status: 404, .. })));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error};
+
+const HEALTH: &str = r#"{"version":"v2.99.0","name":"GoTrue","description":"GoTrue is a user registration and authentication API"}"#;
+
+#[tokio::test]
+async fn it_reads_the_health_of_gotrue() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", HEALTH)]).await;
+
+    let health = Client::new(&server.url).health().await?;
+
+    assert_eq!(health.version, "v2.99.0");
+    assert_eq!(health.name, "GoTrue");
+    assert!(server.requests()[0].starts_with("GET /health "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_waits_until_gotrue_is_ready() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("503 Service Unavailable", "", "{}"),
+        response("200 OK", "", HEALTH),
+    ])
+    .await;
+
+    let health = Client::new(&server.url)
+        .wait_until_ready(Duration::from_secs(5))
+        .await?;
+
+    assert_eq!(health.version, "v2.99.0");
+    assert_eq!(server.hits(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_gives_up_waiting() {
+    let result = Client::new("http://127.0.0.1:1")
+        .wait_until_ready(Duration::from_millis(600))
+        .await;
+
+    assert!(
+        matches!(result, Err(Error::NotReady { waited }) if waited == Duration::from_millis(600))
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error, IdTokenCredentials};
+
+const SESSION: &str = r#"{"access_token":"jwt","token_type":"bearer","expires_in":3600,"refresh_token":"refresh","user":{"id":"1","email":"email@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}}"#;
+
+#[tokio::test]
+async fn it_exchanges_an_id_token_for_a_session() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SESSION)]).await;
+
+    let client = Client::new(&server.url);
+    let credentials = IdTokenCredentials {
+        provider: "google".to_string(),
+        id_token: "id.token".to_string(),
+        nonce: Some("raw-nonce".to_string()),
+        ..IdTokenCredentials::default()
+    };
+    let session = client.sign_in_with_id_token(&credentials).await?;
+
+    assert_eq!(session.access_token, "jwt");
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /token?grant_type=id_token "));
+    assert!(request.contains(r#""provider":"google""#));
+    assert!(request.contains(r#""id_token":"id.token""#));
+    assert!(request.contains(r#""nonce":"raw-nonce""#));
+    assert!(!request.contains("access_token"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_rejects_a_bad_id_token() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "400 Bad Request",
+        "",
+        r#"{"error":"invalid_request","error_description":"Bad ID token"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let credentials = IdTokenCredentials {
+        provider: "apple".to_string(),
+        id_token: "expired".to_string(),
+        ..IdTokenCredentials::default()
+    };
+
+    assert!(matches!(
+        client.sign_in_with_id_token(&credentials).await,
+        Err(Error::WrongToken)
+    ));
+
+    Ok(())
+}
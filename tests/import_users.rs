@@ -0,0 +1,99 @@
+mod common;
+
+use common::{response, serve};
+use futures_util::{stream, StreamExt};
+use go_true_redux::{AdminUserAttributes, Client, Error, ImportOutcome};
+
+const CREATED: &str = r#"{"id":"1","email":"a@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+const EXISTS: &str =
+    r#"{"code":422,"msg":"A user with this email address has already been registered"}"#;
+const INVALID: &str = r#"{"code":400,"msg":"Unable to validate email address: invalid format"}"#;
+
+fn attributes(email: &str) -> AdminUserAttributes {
+    AdminUserAttributes {
+        email: Some(email.to_string()),
+        password_hash: Some("$2a$10$abcdefghijklmnopqrstuv".to_string()),
+        email_confirm: Some(true),
+        ..AdminUserAttributes::default()
+    }
+}
+
+#[tokio::test]
+async fn it_reports_every_record() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", CREATED),
+        response("422 Unprocessable Entity", "", EXISTS),
+        response("400 Bad Request", "", INVALID),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let users = vec![
+        attributes("a@example.com"),
+        attributes("b@example.com"),
+        attributes("invalid"),
+    ];
+    let report = client.import_users(stream::iter(users), 1).await;
+
+    assert_eq!(report.records.len(), 3);
+    assert!(matches!(
+        report.records[0].outcome,
+        ImportOutcome::Created(_)
+    ));
+    assert!(matches!(report.records[1].outcome, ImportOutcome::Skipped));
+    assert_eq!(report.records[1].email.as_deref(), Some("b@example.com"));
+
+    let failed: Vec<_> = report.failed().collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0.index, 2);
+    assert!(matches!(failed[0].1, Error::Api { status: 400, .. }));
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /admin/users "));
+    assert!(request.contains(r#""password_hash":"$2a$10$abcdefghijklmnopqrstuv""#));
+    assert!(!request.contains(r#""password":"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_keeps_input_order_with_concurrency() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", CREATED)]).await;
+
+    let client = Client::new(&server.url);
+    let users = (0..20).map(|i| attributes(&format!("user{i}@example.com")));
+    let report = client.import_users(stream::iter(users), 4).await;
+
+    assert_eq!(server.hits(), 20);
+    assert_eq!(report.created().count(), 20);
+    let indexes: Vec<_> = report.records.iter().map(|record| record.index).collect();
+    assert_eq!(indexes, (0..20).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_streams_records_as_they_complete() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", CREATED),
+        response("400 Bad Request", "", INVALID),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let users = vec![attributes("a@example.com"), attributes("invalid")];
+    let records: Vec<_> = client
+        .import_users_stream(stream::iter(users), 1)
+        .collect()
+        .await;
+
+    assert_eq!(records.len(), 2);
+    assert!(matches!(records[0].outcome, ImportOutcome::Created(_)));
+    assert_eq!(records[1].email.as_deref(), Some("invalid"));
+    assert!(matches!(
+        records[1].outcome,
+        ImportOutcome::Failed(Error::Api { status: 400, .. })
+    ));
+
+    Ok(())
+}
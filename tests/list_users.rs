@@ -0,0 +1,153 @@
+mod common;
+
+use common::{response, serve};
+use futures_util::TryStreamExt;
+use go_true_redux::{Client, ListUsersQuery, SortDirection};
+
+fn user(email: &str) -> String {
+    format!(
+        r#"{{"id":"{email}","email":"{email}","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}}"#
+    )
+}
+
+fn page(emails: &[&str]) -> String {
+    let users: Vec<String> = emails.iter().map(|email| user(email)).collect();
+    format!(r#"{{"aud":"authenticated","users":[{}]}}"#, users.join(","))
+}
+
+#[tokio::test]
+async fn it_sends_the_typed_query() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "200 OK",
+        "x-total-count: 1\r\n",
+        &page(&["a@example.com"]),
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let query = ListUsersQuery::default()
+        .with_page(2)
+        .with_per_page(10)
+        .with_filter("example")
+        .with_sort("created_at", SortDirection::Descending);
+    let users = client.list_users(Some(query)).await?;
+
+    assert_eq!(users.users.len(), 1);
+    assert_eq!(users.total, Some(1));
+    assert_eq!(users.next_page, None);
+
+    let request = &server.requests()[0];
+    assert!(request
+        .starts_with("GET /admin/users?page=2&per_page=10&filter=example&sort=created_at+desc "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_reads_the_next_page_from_the_link_header() -> anyhow::Result<()> {
+    let link = "link: </admin/users?page=2&per_page=1>; rel=\"next\", </admin/users?page=3&per_page=1>; rel=\"last\"\r\n";
+    let server = serve(vec![response("200 OK", link, &page(&["a@example.com"]))]).await;
+
+    let client = Client::new(&server.url);
+    let users = client.list_users(None).await?;
+
+    assert_eq!(users.next_page, Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_streams_every_page() -> anyhow::Result<()> {
+    let next = |page: u32| {
+        format!("x-total-count: 5\r\nlink: </admin/users?page={page}&per_page=2>; rel=\"next\"\r\n")
+    };
+    let server = serve(vec![
+        response(
+            "200 OK",
+            &next(2),
+            &page(&["a@example.com", "b@example.com"]),
+        ),
+        response(
+            "200 OK",
+            &next(3),
+            &page(&["c@example.com", "d@example.com"]),
+        ),
+        response("200 OK", "x-total-count: 5\r\n", &page(&["e@example.com"])),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let users: Vec<_> = client
+        .list_all_users(ListUsersQuery::default().with_per_page(2))
+        .try_collect()
+        .await?;
+
+    let emails: Vec<_> = users.iter().map(|user| user.email.as_str()).collect();
+    assert_eq!(
+        emails,
+        [
+            "a@example.com",
+            "b@example.com",
+            "c@example.com",
+            "d@example.com",
+            "e@example.com"
+        ]
+    );
+    assert_eq!(server.hits(), 3);
+    assert!(server.requests()[2].starts_with("GET /admin/users?page=3&per_page=2 "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_stops_streaming_on_a_short_page_without_headers() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", &page(&["a@example.com", "b@example.com"])),
+        response("200 OK", "", &page(&["c@example.com"])),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let users: Vec<_> = client
+        .list_all_users(ListUsersQuery::default().with_per_page(2))
+        .try_collect()
+        .await?;
+
+    assert_eq!(users.len(), 3);
+    assert_eq!(server.hits(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_reads_absolute_links_too() -> anyhow::Result<()> {
+    let link = "link: <http://localhost/admin/users?page=2&per_page=1>; rel=\"next\"\r\n";
+    let server = serve(vec![response("200 OK", link, &page(&["a@example.com"]))]).await;
+
+    let client = Client::new(&server.url);
+    let users = client.list_users(None).await?;
+
+    assert_eq!(users.next_page, Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_stops_streaming_on_an_empty_page() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "x-total-count: 2\r\n", &page(&["a@example.com"])),
+        response("200 OK", "x-total-count: 2\r\n", &page(&[])),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let users: Vec<_> = client
+        .list_all_users(ListUsersQuery::default().with_per_page(0))
+        .try_collect()
+        .await?;
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(server.hits(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, EmailOtpDelivery, MagicLinkOptions};
+use serde_json::json;
+
+#[tokio::test]
+async fn it_sends_a_magic_link() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = MagicLinkOptions {
+        redirect_to: Some("https://example.com/welcome".to_string()),
+        data: Some(json!({ "plan": "pro" })),
+        captcha_token: Some("captcha".to_string()),
+        ..MagicLinkOptions::default()
+    };
+    let sent = client
+        .sign_in_with_magic_link("email@example.com", &options)
+        .await?;
+
+    assert!(sent);
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /magiclink?redirect_to=https%3A%2F%2Fexample.com%2Fwelcome "));
+    assert!(request.contains(r#""email":"email@example.com""#));
+    assert!(request.contains(r#""data":{"plan":"pro"}"#));
+    assert!(request.contains(r#""gotrue_meta_security":{"captcha_token":"captcha"}"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_sends_a_code_through_otp() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = MagicLinkOptions {
+        delivery: EmailOtpDelivery::Code,
+        ..MagicLinkOptions::default()
+    };
+    client
+        .sign_in_with_magic_link("email@example.com", &options)
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /otp "));
+    assert!(!request.contains("gotrue_meta_security"));
+
+    Ok(())
+}
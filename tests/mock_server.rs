@@ -0,0 +1,236 @@
+use futures_util::{stream, TryStreamExt};
+use go_true_redux::{
+    test_util::MockGoTrue, AdminUserAttributes, Client, EmailOrPhone, Error, ListUsersQuery,
+    Settings, SignOutScope, UserAttributes, VerifyParams,
+};
+
+#[tokio::test]
+async fn it_signs_up_in_and_refreshes() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let client = server.client();
+    let email = EmailOrPhone::Email("email@example.com".to_string());
+
+    let session = client.sign_up(email.clone(), "Abcd1234!").await?;
+    assert!(session.user.email_confirmed_at.is_some());
+
+    assert!(client.sign_in(email.clone(), "wrong").await.is_err());
+    let session = client.sign_in(email, "Abcd1234!").await?;
+
+    let refreshed = client.refresh_access_token(&session.refresh_token).await?;
+    assert_ne!(refreshed.access_token, session.access_token);
+    // Refresh tokens are single use.
+    assert!(client
+        .refresh_access_token(&session.refresh_token)
+        .await
+        .is_err());
+
+    let user = client.get_user(&refreshed.access_token).await?;
+    assert_eq!(user.email, "email@example.com");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_exposes_the_codes_it_sends() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let client = server.client();
+    let phone = "+15555550100".to_string();
+
+    client
+        .send_otp(EmailOrPhone::Phone(phone.clone()), None)
+        .await?;
+    let token = server.otp(&EmailOrPhone::Phone(phone.clone())).unwrap();
+    assert_eq!(token.len(), 6);
+
+    let wrong = client
+        .verify_otp(VerifyParams::Sms {
+            phone: phone.clone(),
+            token: "wrong".to_string(),
+        })
+        .await;
+    assert!(matches!(wrong, Err(Error::WrongToken)));
+
+    let verification = client
+        .verify_otp(VerifyParams::Sms {
+            phone: phone.clone(),
+            token,
+        })
+        .await?;
+    assert_eq!(verification.session.user.phone, phone);
+    assert_eq!(server.otp(&EmailOrPhone::Phone(phone)), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_confirms_sign_ups_without_autoconfirm() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    server.set_settings(Settings {
+        mailer_autoconfirm: false,
+        ..server.settings()
+    });
+    let client = server.client();
+    let email = "email@example.com".to_string();
+
+    // Without autoconfirm GoTrue answers with the user rather than a session.
+    assert!(client
+        .sign_up(EmailOrPhone::Email(email.clone()), "Abcd1234!")
+        .await
+        .is_err());
+    assert!(client
+        .sign_in(EmailOrPhone::Email(email.clone()), "Abcd1234!")
+        .await
+        .is_err());
+
+    let token = server.otp(&EmailOrPhone::Email(email.clone())).unwrap();
+    client
+        .verify_otp(VerifyParams::Signup {
+            email: email.clone(),
+            token,
+        })
+        .await?;
+    client
+        .sign_in(EmailOrPhone::Email(email), "Abcd1234!")
+        .await?;
+
+    assert!(!client.get_settings().await?.mailer_autoconfirm);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_changes_emails_and_signs_out() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let client = server.client();
+
+    let first = client
+        .sign_up(
+            EmailOrPhone::Email("old@example.com".to_string()),
+            "Abcd1234!",
+        )
+        .await?;
+    let second = client
+        .sign_in(
+            EmailOrPhone::Email("old@example.com".to_string()),
+            "Abcd1234!",
+        )
+        .await?;
+
+    let user = client
+        .update_user(
+            UserAttributes::default().with_email("new@example.com"),
+            &first.access_token,
+        )
+        .await?;
+    assert_eq!(user.new_email.as_deref(), Some("new@example.com"));
+
+    let token = server
+        .otp(&EmailOrPhone::Email("new@example.com".to_string()))
+        .unwrap();
+    client
+        .verify_email_change("new@example.com", &token)
+        .await?;
+    assert_eq!(server.users()[0].email, "new@example.com");
+
+    client
+        .sign_out_with_scope(&first.access_token, SignOutScope::Others)
+        .await?;
+    assert!(client.get_user(&first.access_token).await.is_ok());
+    assert!(client.get_user(&second.access_token).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_manages_users_as_admin() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+
+    let anonymous = Client::new(server.url());
+    assert!(matches!(
+        anonymous.list_users(None).await,
+        Err(Error::NotAuthenticated)
+    ));
+
+    let admin = server.admin_client();
+    for i in 0..5 {
+        admin
+            .admin_create_user(&AdminUserAttributes {
+                email: Some(format!("user{i}@example.com")),
+                password: Some("Abcd1234!".to_string()),
+                email_confirm: Some(true),
+                ..AdminUserAttributes::default()
+            })
+            .await?;
+    }
+
+    let duplicate = admin
+        .admin_create_user(&AdminUserAttributes {
+            email: Some("user0@example.com".to_string()),
+            ..AdminUserAttributes::default()
+        })
+        .await;
+    assert!(matches!(duplicate, Err(Error::AlreadySignedUp)));
+
+    let users: Vec<_> = admin
+        .list_all_users(ListUsersQuery::default().with_per_page(2))
+        .try_collect()
+        .await?;
+    assert_eq!(users.len(), 5);
+
+    let id = users[0].id.clone();
+    admin.delete_user(&id).await?;
+    assert!(admin.get_user_by_id(&id).await.is_err());
+    assert_eq!(server.users().len(), 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_imports_users_with_password_hashes() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let admin = server.admin_client();
+    let client = server.client();
+    let email = EmailOrPhone::Email("legacy@example.com".to_string());
+    let hash = "$2a$10$abcdefghijklmnopqrstuv";
+
+    client.sign_up(email.clone(), "Abcd1234!").await?;
+
+    let users = vec![
+        AdminUserAttributes {
+            email: Some("legacy@example.com".to_string()),
+            password_hash: Some(hash.to_string()),
+            ..Default::default()
+        },
+        AdminUserAttributes {
+            email: Some("imported@example.com".to_string()),
+            password_hash: Some(hash.to_string()),
+            email_confirm: Some(true),
+            ..Default::default()
+        },
+    ];
+    let report = admin.import_users(stream::iter(users), 2).await;
+
+    assert_eq!(report.skipped().count(), 1);
+    assert_eq!(report.created().count(), 1);
+    assert_eq!(report.failed().count(), 0);
+
+    // The hash is kept as is, and cannot be signed in with.
+    let imported = EmailOrPhone::Email("imported@example.com".to_string());
+    assert_eq!(server.password_hash(&imported).as_deref(), Some(hash));
+    assert!(client.sign_in(imported.clone(), hash).await.is_err());
+
+    let id = report.created().next().unwrap().id.clone();
+    admin
+        .update_user_by_id(
+            &id,
+            &AdminUserAttributes {
+                password: Some("Abcd1234!".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(server.password_hash(&imported), None);
+    client.sign_in(imported, "Abcd1234!").await?;
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
-use std::{collections::BTreeMap, error::Error};
+use std::collections::BTreeMap;
 
-use go_true_redux::{Client, Settings, User};
+use go_true_redux::{AdminUserAttributes, Client, Settings, User};
 
 use hmac::{Hmac, Mac};
 use jwt::SignWithKey;
@@ -52,6 +52,25 @@ async fn it_should_create_user() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn it_should_admin_create_user() -> anyhow::Result<()> {
+    let client: Client = get_service_api_client();
+    let email = get_random_email();
+    let attributes = AdminUserAttributes {
+        email: Some(email.clone()),
+        password: Some("Abcd1234!".to_owned()),
+        email_confirm: Some(true),
+        ..AdminUserAttributes::default()
+    };
+
+    let response = client.admin_create_user(&attributes).await?;
+
+    assert_eq!(response.email, email);
+    assert!(response.email_confirmed_at.is_some());
+
+    Ok(())
+}
+
 fn get_random_email() -> String {
     let random_string: String = rand::thread_rng()
         .sample_iter(&Alphanumeric)
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, EmailOrPhone, OtpChannel, OtpOptions};
+use serde_json::json;
+
+#[tokio::test]
+async fn it_sends_create_user_by_default() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    client
+        .send_otp(EmailOrPhone::Email("email@example.com".to_string()), None)
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /otp "));
+    assert!(request.contains(r#""create_user":true"#));
+    assert!(!request.contains("should_create_user"));
+    assert!(!request.contains("channel"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_sends_every_email_option() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = OtpOptions {
+        create_user: false,
+        data: Some(json!({ "plan": "pro" })),
+        email_redirect_to: Some("https://example.com/welcome".to_string()),
+        captcha_token: Some("captcha".to_string()),
+        ..OtpOptions::default()
+    };
+    client
+        .send_otp(
+            EmailOrPhone::Email("email@example.com".to_string()),
+            Some(options),
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /otp?redirect_to=https%3A%2F%2Fexample.com%2Fwelcome "));
+    assert!(request.contains(r#""create_user":false"#));
+    assert!(request.contains(r#""data":{"plan":"pro"}"#));
+    assert!(request.contains(r#""gotrue_meta_security":{"captcha_token":"captcha"}"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_sends_the_phone_channel() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = OtpOptions {
+        channel: OtpChannel::Whatsapp,
+        email_redirect_to: Some("https://example.com/welcome".to_string()),
+        ..OtpOptions::default()
+    };
+    client
+        .send_otp(
+            EmailOrPhone::Phone("+15555550100".to_string()),
+            Some(options),
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /otp "));
+    assert!(request.contains(r#""phone":"+15555550100""#));
+    assert!(request.contains(r#""channel":"whatsapp""#));
+
+    Ok(())
+}
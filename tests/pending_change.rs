@@ -0,0 +1,99 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, EmailChangeConfirmation, Error};
+
+const PENDING: &str = r#"{"id":"1","email":"old@example.com","new_email":"new@example.com","email_change_sent_at":"2023-01-01T00:00:00Z","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+const SETTLED: &str = r#"{"id":"1","email":"old@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+const SESSION: &str = r#"{"access_token":"jwt","token_type":"bearer","expires_in":3600,"refresh_token":"refresh","user":{"id":"1","email":"new@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}}"#;
+
+#[tokio::test]
+async fn it_resends_the_pending_email_change() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", PENDING),
+        response("200 OK", "", PENDING),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let user = client.resend_email_change("jwt").await?;
+
+    assert_eq!(user.new_email.as_deref(), Some("new@example.com"));
+    assert!(user.email_change_sent_at.is_some());
+
+    let requests = server.requests();
+    assert!(requests[0].starts_with("GET /user "));
+    assert!(requests[1].starts_with("PUT /user "));
+    assert!(requests[1].contains(r#"{"email":"new@example.com"}"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_without_a_pending_change() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SETTLED)]).await;
+
+    let client = Client::new(&server.url);
+
+    assert!(matches!(
+        client.resend_email_change("jwt").await,
+        Err(Error::NoPendingChange)
+    ));
+    assert!(matches!(
+        client.resend_phone_change("jwt").await,
+        Err(Error::NoPendingChange)
+    ));
+    assert_eq!(server.hits(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_waits_for_the_other_address() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response(
+            "200 OK",
+            "",
+            r#"{"msg":"Confirmation link accepted. Please proceed to confirm link sent to the other email","code":200}"#,
+        ),
+        response("200 OK", "", SESSION),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+
+    let first = client
+        .verify_email_change("old@example.com", "111111")
+        .await?;
+    assert!(matches!(
+        first,
+        EmailChangeConfirmation::AwaitingOtherAddress
+    ));
+
+    let second = client
+        .verify_email_change("new@example.com", "222222")
+        .await?;
+    match second {
+        EmailChangeConfirmation::Confirmed(session) => {
+            assert_eq!(session.user.email, "new@example.com")
+        }
+        other => panic!("expected a session, got {other:?}"),
+    }
+
+    assert!(server.requests()[1].contains(r#""type":"email_change""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_verifies_a_phone_change() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SESSION)]).await;
+
+    let client = Client::new(&server.url);
+    let session = client.verify_phone_change("+15555550100", "123456").await?;
+
+    assert_eq!(session.access_token, "jwt");
+    assert!(server.requests()[0].contains(r#""type":"phone_change""#));
+
+    Ok(())
+}
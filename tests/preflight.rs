@@ -0,0 +1,159 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{response, serve};
+use go_true_redux::{
+    Capability, Client, EmailOrPhone, EmailOtpDelivery, Error, MagicLinkOptions, Provider,
+    SettingsCache,
+};
+
+const SETTINGS: &str = r#"{
+    "external": {"email": true, "phone": false, "github": true, "google": false},
+    "disable_signup": false,
+    "mailer_autoconfirm": true
+}"#;
+
+const SIGNUP_DISABLED: &str =
+    r#"{"external": {"email": true, "phone": true}, "disable_signup": true}"#;
+
+#[tokio::test]
+async fn it_fails_fast_for_disabled_channels_and_providers() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SETTINGS)]).await;
+    let client = Client::new(&server.url).with_settings_cache(SettingsCache::default());
+    let phone = EmailOrPhone::Phone("+15555550100".to_string());
+
+    assert!(matches!(
+        client.sign_up(phone.clone(), "Abcd1234!").await,
+        Err(Error::Disabled {
+            capability: Capability::Phone
+        })
+    ));
+    assert!(matches!(
+        client.send_otp(phone, None).await,
+        Err(Error::Disabled {
+            capability: Capability::Phone
+        })
+    ));
+    assert!(matches!(
+        client.get_checked_url_for_provider("google").await,
+        Err(Error::Disabled {
+            capability: Capability::Provider(Provider::Google)
+        })
+    ));
+    assert!(client
+        .get_checked_url_for_provider("Github")
+        .await?
+        .ends_with("/authorize?provider=github"));
+    assert!(client
+        .get_checked_url_for_provider("custom_idp")
+        .await?
+        .ends_with("/authorize?provider=custom_idp"));
+
+    // The settings were fetched once and nothing else was called.
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_fast_when_signup_is_disabled() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SIGNUP_DISABLED)]).await;
+    let client = Client::new(&server.url).with_settings_cache(SettingsCache::default());
+    let email = EmailOrPhone::Email("email@example.com".to_string());
+
+    assert!(matches!(
+        client.sign_up(email, "Abcd1234!").await,
+        Err(Error::Disabled {
+            capability: Capability::Signup
+        })
+    ));
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_fast_for_magic_links_when_email_is_disabled() -> anyhow::Result<()> {
+    let settings = r#"{"external": {"email": false, "phone": true}}"#;
+    let server = serve(vec![response("200 OK", "", settings)]).await;
+    let client = Client::new(&server.url).with_settings_cache(SettingsCache::default());
+
+    for delivery in [EmailOtpDelivery::Link, EmailOtpDelivery::Code] {
+        let options = MagicLinkOptions {
+            delivery,
+            ..MagicLinkOptions::default()
+        };
+        assert!(matches!(
+            client
+                .sign_in_with_magic_link("email@example.com", &options)
+                .await,
+            Err(Error::Disabled {
+                capability: Capability::Email
+            })
+        ));
+    }
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fetches_the_settings_again_once_stale() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", SIGNUP_DISABLED),
+        response("200 OK", "", SETTINGS),
+        response(
+            "200 OK",
+            "",
+            r#"{"id":"user-id","email":"email@example.com"}"#,
+        ),
+    ])
+    .await;
+    let cache = SettingsCache::new(Duration::from_millis(50));
+    let client = Client::new(&server.url).with_settings_cache(cache.clone());
+    let email = EmailOrPhone::Email("email@example.com".to_string());
+
+    assert!(client.sign_up(email.clone(), "Abcd1234!").await.is_err());
+    assert!(cache.get().unwrap().disable_signup);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(cache.get(), None);
+
+    // The refreshed settings allow the call, which then reaches GoTrue.
+    let _ = client.sign_up(email, "Abcd1234!").await;
+    assert_eq!(server.hits(), 3);
+    assert!(server.requests()[2].starts_with("POST /signup"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_checks_nothing_without_a_cache() -> anyhow::Result<()> {
+    let server = serve(vec![]).await;
+    let client = Client::new(&server.url);
+
+    client.get_checked_url_for_provider("google").await?;
+    assert_eq!(server.hits(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_skips_channels_the_instance_does_not_report() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", r#"{"external": {"github": true}}"#),
+        response("200 OK", "", "{}"),
+    ])
+    .await;
+    let client = Client::new(&server.url).with_settings_cache(SettingsCache::default());
+
+    client
+        .send_otp(EmailOrPhone::Phone("+15555550100".to_string()), None)
+        .await?;
+
+    assert_eq!(server.hits(), 2);
+    assert!(server.requests()[1].starts_with("POST /otp"));
+
+    Ok(())
+}
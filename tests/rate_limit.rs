@@ -0,0 +1,96 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{response, serve};
+use go_true_redux::{Client, EmailOrPhone, Error, RateLimitTracker};
+
+const RATE_LIMITED: &str =
+    r#"{"code":429,"msg":"For security purposes, you can only request this after 42 seconds."}"#;
+
+#[tokio::test]
+async fn it_returns_rate_limited_with_the_remaining_seconds() -> anyhow::Result<()> {
+    let server = serve(vec![response("429 Too Many Requests", "", RATE_LIMITED)]).await;
+
+    let client = Client::new(&server.url);
+    let result = client
+        .send_otp(EmailOrPhone::Email("email@example.com".to_string()), None)
+        .await;
+
+    match result {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(42)))
+        }
+        other => panic!("expected a rate limit error, got {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_prefers_the_retry_after_header() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "429 Too Many Requests",
+        "retry-after: 7\r\n",
+        RATE_LIMITED,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let result = client.reset_password_for_email("email@example.com").await;
+
+    match result {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(7)))
+        }
+        other => panic!("expected a rate limit error, got {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_tracks_cooldowns_per_address() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let tracker = RateLimitTracker::new(Duration::from_secs(60));
+    let client = Client::new(&server.url).with_rate_limit_tracker(tracker.clone());
+    let email = EmailOrPhone::Email("email@example.com".to_string());
+    let other = EmailOrPhone::Email("other@example.com".to_string());
+
+    client.send_otp(email.clone(), None).await?;
+    assert!(tracker.remaining(&email).is_some());
+    assert!(tracker.remaining(&other).is_none());
+
+    let result = client.send_otp(email.clone(), None).await;
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+    assert_eq!(server.hits(), 1);
+
+    client.send_otp(other, None).await?;
+    assert_eq!(server.hits(), 2);
+
+    tracker.clear(&email);
+    client.send_otp(email, None).await?;
+    assert_eq!(server.hits(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_starts_the_cooldown_from_a_rate_limit_response() -> anyhow::Result<()> {
+    let server = serve(vec![response("429 Too Many Requests", "", RATE_LIMITED)]).await;
+
+    let tracker = RateLimitTracker::default();
+    let client = Client::new(&server.url).with_rate_limit_tracker(tracker.clone());
+    let phone = EmailOrPhone::Phone("+15555550100".to_string());
+
+    let _ = client.send_otp(phone.clone(), None).await;
+
+    let remaining = tracker
+        .remaining(&phone)
+        .expect("phone should be cooling down");
+    assert!(remaining <= Duration::from_secs(42));
+    assert!(remaining > Duration::from_secs(40));
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error, UserAttributes};
+
+const UPDATED: &str = r#"{"id":"1","email":"email@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+
+#[tokio::test]
+async fn it_requests_a_nonce() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    assert!(client.reauthenticate("jwt").await?);
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("GET /reauthenticate "));
+    assert!(request.to_lowercase().contains("authorization: bearer jwt"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_reauthentication_required() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "400 Bad Request",
+        "",
+        r#"{"code":400,"msg":"Password update requires reauthentication"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let result = client
+        .update_user(UserAttributes::default().with_password("Abcd12345!"), "jwt")
+        .await;
+
+    assert!(matches!(result, Err(Error::ReauthenticationRequired)));
+    assert!(!server.requests()[0].contains("nonce"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_sends_the_nonce_with_the_update() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", UPDATED)]).await;
+
+    let client = Client::new(&server.url);
+    client
+        .update_user(
+            UserAttributes::default()
+                .with_password("Abcd12345!")
+                .with_nonce("123456"),
+            "jwt",
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("PUT /user "));
+    assert!(request.contains(r#""nonce":"123456""#));
+
+    Ok(())
+}
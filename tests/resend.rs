@@ -0,0 +1,128 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{response, serve};
+use go_true_redux::{
+    test_util::MockGoTrue, Client, EmailOrPhone, Error, RateLimitTracker, ResendOptions,
+    ResendParams, UserAttributes,
+};
+
+#[tokio::test]
+async fn it_resends_a_signup_email() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = ResendOptions {
+        email_redirect_to: Some("https://example.com/welcome".to_string()),
+        captcha_token: Some("captcha".to_string()),
+    };
+    client
+        .resend(
+            ResendParams::Signup {
+                email: "email@example.com".to_string(),
+            },
+            Some(options),
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /resend?redirect_to=https%3A%2F%2Fexample.com%2Fwelcome "));
+    assert!(request.contains(r#""type":"signup""#));
+    assert!(request.contains(r#""email":"email@example.com""#));
+    assert!(request.contains(r#""gotrue_meta_security":{"captcha_token":"captcha""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_resends_a_phone_change_without_redirect() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", "{}")]).await;
+
+    let client = Client::new(&server.url);
+    let options = ResendOptions {
+        email_redirect_to: Some("https://example.com/welcome".to_string()),
+        ..ResendOptions::default()
+    };
+    client
+        .resend(
+            ResendParams::PhoneChange {
+                phone: "+15555550100".to_string(),
+            },
+            Some(options),
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /resend "));
+    assert!(request.contains(r#""type":"phone_change""#));
+    assert!(request.contains(r#""phone":"+15555550100""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_is_rate_limited() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "429 Too Many Requests",
+        "",
+        r#"{"code":429,"msg":"For security purposes, you can only request this after 42 seconds."}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url)
+        .with_rate_limit_tracker(RateLimitTracker::new(Duration::from_secs(60)));
+    let params = ResendParams::EmailChange {
+        email: "current@example.com".to_string(),
+    };
+
+    let result = client.resend(params.clone(), None).await;
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(d)
+        }) if d == Duration::from_secs(42)
+    ));
+
+    // The cooldown is enforced locally without calling GoTrue again.
+    assert!(matches!(
+        client.resend(params, None).await,
+        Err(Error::RateLimited { .. })
+    ));
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_resends_an_email_change_by_the_current_address() -> anyhow::Result<()> {
+    let server = MockGoTrue::start().await?;
+    let client = server.client();
+
+    let current = EmailOrPhone::Email("current@example.com".to_string());
+    let new = EmailOrPhone::Email("new@example.com".to_string());
+    let session = client.sign_up(current, "Abcd1234!").await?;
+    client
+        .update_user(
+            UserAttributes::default().with_email("new@example.com"),
+            &session.access_token,
+        )
+        .await?;
+
+    client
+        .resend(
+            ResendParams::EmailChange {
+                email: "current@example.com".to_string(),
+            },
+            None,
+        )
+        .await?;
+
+    let token = server.otp(&new).unwrap();
+    client
+        .verify_email_change("new@example.com", &token)
+        .await?;
+    assert_eq!(server.users()[0].email, "new@example.com");
+
+    Ok(())
+}
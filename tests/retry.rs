@@ -0,0 +1,101 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{response, serve};
+use go_true_redux::{Client, RetryPolicy};
+
+const SETTINGS: &str = r#"{"external":{"github":true},"disable_signup":false,"autoconfirm":true}"#;
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .with_max_attempts(3)
+        .with_initial_backoff(Duration::from_millis(1))
+        .with_jitter(false)
+}
+
+#[tokio::test]
+async fn it_retries_idempotent_calls_on_unavailable() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("503 Service Unavailable", "", ""),
+        response("502 Bad Gateway", "", ""),
+        response("200 OK", "", SETTINGS),
+    ])
+    .await;
+
+    let client = Client::new(&server.url).with_retry_policy(fast_policy());
+    let settings = client.get_settings().await?;
+
+    assert!(settings.autoconfirm);
+    assert_eq!(server.hits(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_gives_up_after_max_attempts() -> anyhow::Result<()> {
+    let server = serve(vec![response("503 Service Unavailable", "", "")]).await;
+
+    let client = Client::new(&server.url).with_retry_policy(fast_policy());
+    let result = client.list_users(None).await;
+
+    assert!(result.is_err());
+    assert_eq!(server.hits(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_does_not_retry_without_a_policy() -> anyhow::Result<()> {
+    let server = serve(vec![response("503 Service Unavailable", "", "")]).await;
+
+    let client = Client::new(&server.url);
+    let result = client.get_user_by_id("some-id").await;
+
+    assert!(result.is_err());
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_does_not_retry_non_idempotent_calls() -> anyhow::Result<()> {
+    let server = serve(vec![response("503 Service Unavailable", "", "")]).await;
+
+    let client = Client::new(&server.url).with_retry_policy(fast_policy());
+    let result = client.reset_password_for_email("email@example.com").await;
+
+    assert!(result.is_err());
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_stops_when_retry_after_exceeds_max_backoff() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "429 Too Many Requests",
+        "retry-after: 120\r\n",
+        "",
+    )])
+    .await;
+
+    let client = Client::new(&server.url).with_retry_policy(fast_policy());
+    let result = client.list_users(None).await;
+
+    assert!(result.is_err());
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn it_doubles_the_backoff_up_to_the_maximum() {
+    let policy = RetryPolicy::default()
+        .with_initial_backoff(Duration::from_millis(100))
+        .with_max_backoff(Duration::from_millis(350));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Provider, Settings};
+
+const SETTINGS: &str = r#"{
+    "external": {
+        "anonymous_users": false,
+        "email": true,
+        "phone": true,
+        "github": true,
+        "google": false,
+        "custom_idp": true
+    },
+    "disable_signup": false,
+    "mailer_autoconfirm": true,
+    "phone_autoconfirm": false,
+    "sms_provider": "twilio",
+    "mfa_enabled": true,
+    "saml_enabled": false
+}"#;
+
+#[tokio::test]
+async fn it_reads_every_setting() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SETTINGS)]).await;
+
+    let settings = Client::new(&server.url).get_settings().await?;
+
+    assert_eq!(settings.external.email, Some(true));
+    assert_eq!(settings.external.phone, Some(true));
+    assert_eq!(settings.external.anonymous_users, Some(false));
+    assert!(settings.mailer_autoconfirm);
+    assert!(!settings.phone_autoconfirm);
+    assert_eq!(settings.sms_provider.as_deref(), Some("twilio"));
+    assert!(settings.mfa_enabled);
+    assert!(!settings.saml_enabled);
+
+    assert_eq!(settings.supports_provider(Provider::Github), Some(true));
+    assert_eq!(settings.supports_provider(Provider::Google), Some(false));
+    assert_eq!(settings.supports_provider(Provider::Apple), None);
+    assert_eq!(
+        settings.supports_provider(Provider::from("custom_idp")),
+        Some(true)
+    );
+    assert_eq!(settings.can_sign_up_with_email(), Some(true));
+    assert_eq!(settings.can_sign_up_with_phone(), Some(true));
+    assert_eq!(settings.can_sign_in_anonymously(), Some(false));
+    assert!(settings.autoconfirms_email());
+
+    Ok(())
+}
+
+#[test]
+fn it_tolerates_older_and_partial_settings() -> anyhow::Result<()> {
+    let settings: Settings = serde_json::from_str(
+        r#"{"external":{"email":true,"bitbucket":true},"disable_signup":true,"autoconfirm":true}"#,
+    )?;
+
+    assert!(settings.autoconfirms_email());
+    assert_eq!(settings.supports_provider(Provider::Bitbucket), Some(true));
+    assert_eq!(settings.can_sign_up_with_email(), Some(false));
+    assert_eq!(settings.can_sign_up_with_phone(), Some(false));
+    assert_eq!(settings.sms_provider, None);
+
+    Ok(())
+}
+
+#[test]
+fn it_leaves_unreported_channels_unknown() -> anyhow::Result<()> {
+    let settings: Settings = serde_json::from_str(r#"{"external":{"github":true}}"#)?;
+
+    assert_eq!(settings.external.email, None);
+    assert_eq!(settings.external.phone, None);
+    assert_eq!(settings.can_sign_up_with_email(), None);
+    assert_eq!(settings.can_sign_up_with_phone(), None);
+    assert_eq!(settings.supports_provider(Provider::Github), Some(true));
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error, SignOutScope};
+
+#[tokio::test]
+async fn it_signs_out_other_devices() -> anyhow::Result<()> {
+    let server = serve(vec![response("204 No Content", "", "")]).await;
+
+    let client = Client::new(&server.url);
+    client
+        .sign_out_with_scope("jwt", SignOutScope::Others)
+        .await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /logout?scope=others "));
+    assert!(request.to_lowercase().contains("authorization: bearer jwt"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_signs_out_every_device_by_default() -> anyhow::Result<()> {
+    let server = serve(vec![response("204 No Content", "", "")]).await;
+
+    let client = Client::new(&server.url);
+    client.sign_out("jwt").await?;
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /logout?scope=global "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_with_an_invalid_token() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "401 Unauthorized",
+        "",
+        r#"{"code":401,"msg":"invalid JWT"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+
+    assert!(matches!(
+        client
+            .sign_out_with_scope("invalid", SignOutScope::Local)
+            .await,
+        Err(Error::NotAuthenticated)
+    ));
+
+    Ok(())
+}
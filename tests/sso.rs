@@ -0,0 +1,118 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{
+    AttributeMapping, AttributeMappingValue, Client, CodeChallengeMethod, Error, PkceChallenge,
+    SsoOptions, SsoProviderAttributes, SsoTarget,
+};
+
+const PROVIDER: &str = r#"{"id":"p1","saml":{"entity_id":"https://idp.example.com","metadata_url":"https://idp.example.com/metadata","attribute_mapping":{"keys":{"name":{"name":"displayName"}}}},"domains":[{"domain":"example.com"}],"created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+
+#[tokio::test]
+async fn it_returns_the_sso_url() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "200 OK",
+        "",
+        r#"{"url":"https://idp.example.com/sso?SAMLRequest=abc"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let options = SsoOptions {
+        redirect_to: Some("https://example.com/welcome".to_string()),
+        pkce: Some(PkceChallenge {
+            challenge: "challenge".to_string(),
+            method: CodeChallengeMethod::S256,
+        }),
+        ..SsoOptions::default()
+    };
+    let url = client
+        .sign_in_with_sso(SsoTarget::Domain("example.com".to_string()), &options)
+        .await?;
+
+    assert_eq!(url, "https://idp.example.com/sso?SAMLRequest=abc");
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /sso "));
+    assert!(request.contains(r#""domain":"example.com""#));
+    assert!(request.contains(r#""skip_http_redirect":true"#));
+    assert!(request.contains(r#""code_challenge":"challenge""#));
+    assert!(request.contains(r#""code_challenge_method":"s256""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_rejects_a_wrong_auth_code() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "404 Not Found",
+        "",
+        r#"{"code":404,"msg":"No valid flow state found"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+
+    assert!(matches!(
+        client.exchange_code_for_session("code", "verifier").await,
+        Err(Error::WrongToken)
+    ));
+    assert!(server.requests()[0].starts_with("POST /token?grant_type=pkce "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_creates_a_provider_from_a_metadata_url() -> anyhow::Result<()> {
+    let server = serve(vec![response("201 Created", "", PROVIDER)]).await;
+
+    let client = Client::new(&server.url);
+    let mut mapping = AttributeMapping::default();
+    mapping.keys.insert(
+        "name".to_string(),
+        AttributeMappingValue {
+            name: Some("displayName".to_string()),
+            ..AttributeMappingValue::default()
+        },
+    );
+    let attributes = SsoProviderAttributes {
+        metadata_url: Some("https://idp.example.com/metadata".to_string()),
+        attribute_mapping: Some(mapping.clone()),
+        domains: Some(vec!["example.com".to_string()]),
+        ..SsoProviderAttributes::default()
+    };
+    let provider = client.create_sso_provider(&attributes).await?;
+
+    assert_eq!(provider.id, "p1");
+    assert_eq!(provider.domains[0].domain, "example.com");
+    assert_eq!(provider.saml.unwrap().attribute_mapping, Some(mapping));
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /admin/sso/providers "));
+    assert!(request.contains(r#""type":"saml""#));
+    assert!(request.contains(r#""domains":["example.com"]"#));
+    assert!(!request.contains("metadata_xml"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_lists_and_deletes_providers() -> anyhow::Result<()> {
+    let server = serve(vec![
+        response("200 OK", "", &format!(r#"{{"items":[{PROVIDER}]}}"#)),
+        response("200 OK", "", PROVIDER),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let providers = client.list_sso_providers().await?;
+    assert_eq!(providers.len(), 1);
+
+    client.delete_sso_provider(&providers[0].id).await?;
+
+    let requests = server.requests();
+    assert!(requests[0].starts_with("GET /admin/sso/providers "));
+    assert!(requests[1].starts_with("DELETE /admin/sso/providers/p1 "));
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+use go_true_redux::{
+    http, Client, EmailOrPhone, Error, HttpRequest, HttpResponse, HttpTransport, RetryPolicy,
+    TransportError, TransportErrorKind,
+};
+
+/// Answers with queued responses and keeps the requests it was sent.
+#[derive(Debug, Default)]
+struct QueueTransport {
+    requests: Mutex<Vec<HttpRequest>>,
+    responses: Mutex<VecDeque<Result<HttpResponse, TransportError>>>,
+}
+
+impl QueueTransport {
+    fn reply(&self, status: u16, body: &str) {
+        let response = http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(body.as_bytes().to_vec())
+            .unwrap();
+        self.responses.lock().unwrap().push_back(Ok(response));
+    }
+
+    fn fail(&self, kind: TransportErrorKind) {
+        let error = TransportError::new(kind, "connection refused");
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
+}
+
+impl HttpTransport for QueueTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        self.requests.lock().unwrap().push(request);
+        let response = self.responses.lock().unwrap().pop_front().unwrap();
+        Box::pin(async move { response })
+    }
+}
+
+const SESSION: &str = r#"{"access_token":"access","token_type":"bearer","expires_in":3600,"refresh_token":"refresh","user":{"id":"1","aud":"authenticated","role":"authenticated","email":"email@example.com","phone":"","confirmation_sent_at":null,"app_metadata":{},"user_metadata":{},"identities":[],"created_at":"","updated_at":""}}"#;
+
+#[tokio::test]
+async fn it_sends_requests_through_the_transport() {
+    let transport = Arc::new(QueueTransport::default());
+    transport.reply(200, SESSION);
+
+    let client = Client::with_transport("http://gotrue.test", transport.clone())
+        .with_header("apikey", "key");
+    let session = client
+        .sign_in(EmailOrPhone::Email("email@example.com".into()), "Abcd1234!")
+        .await
+        .unwrap();
+
+    assert_eq!(session.access_token, "access");
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method(), http::Method::POST);
+    assert_eq!(
+        requests[0].uri().to_string(),
+        "http://gotrue.test/token?grant_type=password"
+    );
+    assert_eq!(requests[0].headers()["apikey"], "key");
+
+    let body: serde_json::Value = serde_json::from_slice(requests[0].body()).unwrap();
+    assert_eq!(body["email"], "email@example.com");
+}
+
+#[tokio::test]
+async fn it_maps_error_responses_to_errors() {
+    let transport = Arc::new(QueueTransport::default());
+    transport.reply(
+        400,
+        r#"{"error":"invalid_grant","error_description":"Invalid login credentials"}"#,
+    );
+
+    let client = Client::with_transport("http://gotrue.test", transport);
+    let result = client
+        .sign_in(EmailOrPhone::Email("email@example.com".into()), "wrong")
+        .await;
+
+    assert!(
+        matches!(result, Err(Error::Api { status: 400, message }) if message == "Invalid login credentials")
+    );
+}
+
+#[tokio::test]
+async fn it_retries_transport_errors() {
+    let transport = Arc::new(QueueTransport::default());
+    transport.fail(TransportErrorKind::Connect);
+    transport.reply(
+        200,
+        r#"{"external":{},"disable_signup":false,"autoconfirm":true}"#,
+    );
+
+    let client = Client::with_transport("http://gotrue.test", transport.clone()).with_retry_policy(
+        RetryPolicy::default()
+            .with_max_attempts(2)
+            .with_initial_backoff(Duration::ZERO),
+    );
+    let settings = client.get_settings().await.unwrap();
+
+    assert!(settings.autoconfirm);
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+}
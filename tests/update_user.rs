@@ -0,0 +1,53 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, UserAttributes};
+use serde_json::json;
+
+const UPDATED: &str = r#"{"id":"1","email":"email@example.com","new_email":"new@example.com","aud":"authenticated","role":"authenticated","phone":"","user_metadata":{"plan":"pro"},"created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+
+#[tokio::test]
+async fn it_only_sends_the_attributes_that_are_set() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", UPDATED)]).await;
+
+    let client = Client::new(&server.url);
+    client
+        .update_user(
+            UserAttributes::default().with_data(json!({ "plan": "pro" })),
+            "jwt",
+        )
+        .await?;
+
+    let request = &server.requests()[0];
+    let body = request.split("\r\n\r\n").nth(1).unwrap_or_default();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(body)?,
+        json!({ "data": { "plan": "pro" } })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_the_updated_user() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", UPDATED)]).await;
+
+    let client = Client::new(&server.url);
+    let user = client
+        .update_user(
+            UserAttributes::default()
+                .with_email("new@example.com")
+                .with_email_redirect_to("https://example.com/confirmed"),
+            "jwt",
+        )
+        .await?;
+
+    assert_eq!(user.new_email.as_deref(), Some("new@example.com"));
+    assert_eq!(user.user_metadata, Some(json!({ "plan": "pro" })));
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("PUT /user?redirect_to=https%3A%2F%2Fexample.com%2Fconfirmed "));
+    assert!(!request.contains("email_redirect_to"));
+
+    Ok(())
+}
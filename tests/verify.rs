@@ -0,0 +1,184 @@
+mod common;
+
+use common::{response, serve};
+use go_true_redux::{Client, Error, VerificationType, VerifyParams};
+
+const SESSION: &str = r#"{"access_token":"jwt","token_type":"bearer","expires_in":3600,"refresh_token":"refresh","user":{"id":"1","email":"","aud":"authenticated","role":"authenticated","phone":"15555550100","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}}"#;
+
+#[tokio::test]
+async fn it_verifies_an_sms_otp() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SESSION)]).await;
+
+    let client = Client::new(&server.url);
+    let verification = client
+        .verify_otp(VerifyParams::Sms {
+            phone: "+15555550100".to_string(),
+            token: "123456".to_string(),
+        })
+        .await?;
+
+    assert_eq!(verification.session.access_token, "jwt");
+    assert_eq!(verification.verification_type, VerificationType::Sms);
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /verify "));
+    assert!(request.contains(r#""type":"sms""#));
+    assert!(request.contains(r#""phone":"+15555550100""#));
+    assert!(request.contains(r#""token":"123456""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_verifies_a_token_hash() -> anyhow::Result<()> {
+    let server = serve(vec![response("200 OK", "", SESSION)]).await;
+
+    let client = Client::new(&server.url);
+    let verification = client
+        .verify_otp(VerifyParams::TokenHash {
+            verification_type: VerificationType::MagicLink,
+            token_hash: "hashed".to_string(),
+        })
+        .await?;
+
+    assert_eq!(verification.verification_type, VerificationType::MagicLink);
+
+    let request = &server.requests()[0];
+    assert!(request.contains(r#""type":"magiclink""#));
+    assert!(request.contains(r#""token_hash":"hashed""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_wrong_token_for_an_expired_otp() -> anyhow::Result<()> {
+    let server = serve(vec![response(
+        "403 Forbidden",
+        "",
+        r#"{"code":403,"msg":"Token has expired or is invalid"}"#,
+    )])
+    .await;
+
+    let client = Client::new(&server.url);
+    let result = client
+        .verify_otp(VerifyParams::EmailChange {
+            email: "email@example.com".to_string(),
+            token: "123456".to_string(),
+        })
+        .await;
+
+    assert!(matches!(result, Err(Error::WrongToken)));
+    assert!(server.requests()[0].contains(r#""type":"email_change""#));
+
+    Ok(())
+}
+
+const USER: &str = r#"{"id":"1","email":"email@example.com","aud":"authenticated","role":"authenticated","phone":"","created_at":"2023-01-01T00:00:00Z","updated_at":"2023-01-01T00:00:00Z"}"#;
+
+#[tokio::test]
+async fn it_follows_a_verification_link() -> anyhow::Result<()> {
+    let location = "location: http://localhost:3000/#access_token=jwt&token_type=bearer&expires_in=3600&refresh_token=refresh&type=invite\r\n";
+    let server = serve(vec![
+        response("303 See Other", location, ""),
+        response("200 OK", "", USER),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let link = format!(
+        "{}/verify?token=abc&type=invite&redirect_to=http://localhost:3000",
+        server.url
+    );
+    let verification = client.verify_link(&link).await?;
+
+    assert_eq!(verification.verification_type, VerificationType::Invite);
+    assert_eq!(verification.session.access_token, "jwt");
+    assert_eq!(verification.session.refresh_token, "refresh");
+    assert_eq!(verification.session.expires_in, 3600);
+    assert_eq!(verification.session.user.email, "email@example.com");
+
+    let requests = server.requests();
+    assert!(requests[0].starts_with("GET /verify?token=abc&type=invite"));
+    assert!(requests[1].starts_with("GET /user "));
+    assert!(requests[1]
+        .to_lowercase()
+        .contains("authorization: bearer jwt"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_wrong_token_for_an_expired_link() -> anyhow::Result<()> {
+    let location = "location: http://localhost:3000/#error=unauthorized_client&error_code=403&error_description=Email+link+is+invalid+or+has+expired\r\n";
+    let server = serve(vec![response("303 See Other", location, "")]).await;
+
+    let client = Client::new(&server.url);
+    let link = format!("{}/verify?token=abc&type=magiclink", server.url);
+    let result = client.verify_link(&link).await;
+
+    assert!(matches!(result, Err(Error::WrongToken)));
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_wrong_token_for_a_string_error_code() -> anyhow::Result<()> {
+    let location = "location: http://localhost:3000/#error=access_denied&error_code=otp_expired&error_description=Email+link+is+invalid+or+has+expired\r\n";
+    let server = serve(vec![response("303 See Other", location, "")]).await;
+
+    let client = Client::new(&server.url);
+    let link = format!("{}/verify?token=abc&type=magiclink", server.url);
+    let result = client.verify_link(&link).await;
+
+    assert!(matches!(result, Err(Error::WrongToken)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_returns_wrong_token_when_access_is_denied() -> anyhow::Result<()> {
+    let location = "location: http://localhost:3000/?error=access_denied&error_description=Token+has+expired\r\n";
+    let server = serve(vec![response("303 See Other", location, "")]).await;
+
+    let client = Client::new(&server.url);
+    let link = format!("{}/verify?token=abc&type=signup", server.url);
+    let result = client.verify_link(&link).await;
+
+    assert!(matches!(result, Err(Error::WrongToken)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_refuses_links_to_other_hosts() -> anyhow::Result<()> {
+    let server = serve(vec![]).await;
+
+    let client = Client::new("http://localhost:9998").with_header("apikey", "service.role.jwt");
+    let link = format!("{}/verify?token=abc&type=magiclink", server.url);
+    let result = client.verify_link(&link).await;
+
+    assert!(matches!(result, Err(Error::WrongToken)));
+    assert_eq!(server.hits(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_fails_for_a_link_without_a_type() -> anyhow::Result<()> {
+    let location = "location: http://localhost:3000/#access_token=jwt&token_type=bearer&expires_in=3600&refresh_token=refresh\r\n";
+    let server = serve(vec![
+        response("303 See Other", location, ""),
+        response("200 OK", "", USER),
+    ])
+    .await;
+
+    let client = Client::new(&server.url);
+    let link = format!("{}/verify?token=abc", server.url);
+    let result = client.verify_link(&link).await;
+
+    assert!(matches!(result, Err(Error::InternalError)));
+    assert_eq!(server.hits(), 1);
+
+    Ok(())
+}